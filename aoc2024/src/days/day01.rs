@@ -1,53 +1,76 @@
-use crate::input;
-use regex::Regex;
+use std::collections::HashMap;
 
-const NUMBERS_REGEX: &str = r#"(?<left>\d+) +(?<right>\d+)"#;
+use aoc_macros::{aoc, aoc_parser, FromInput};
+use aoc_utils::solution::Answer;
+use aoc_utils::{parse, registry};
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_1_INPUT;
-    let numbers_re = Regex::new(NUMBERS_REGEX).unwrap();
-
-    let mut left_numbers: Vec<isize> = Vec::new();
-    let mut right_numbers: Vec<isize> = Vec::new();
-
-    // This is an ugly way to extract numbers:
-    let captures = numbers_re.captures_iter(input);
+pub fn solve(input: &str) -> (Option<Answer>, Option<Answer>) {
+    registry::solve(env!("CARGO_CRATE_NAME"), 1, input)
+}
 
-    for value in captures {
-        let left = value.name("left");
-        let right = value.name("right");
+/// Left and right lists of location IDs, sorted
+struct Lists {
+    left: Vec<isize>,
+    right: Vec<isize>,
+}
 
-        if left.is_none() || right.is_none() {
-            eprintln!("Error parsing line");
-            continue;
-        }
+/// A line of the input, one number of each list
+#[derive(FromInput)]
+#[input("{left} {right}")]
+struct Pair {
+    left: isize,
+    right: isize,
+}
 
-        let left: isize = left.unwrap().as_str().parse::<isize>().unwrap();
-        let right: isize = right.unwrap().as_str().parse::<isize>().unwrap();
+#[aoc_parser(day = 1)]
+fn parse(input: &str) -> Lists {
+    let pairs: Vec<Pair> = parse::lines(input).expect("Invalid lists");
 
-        left_numbers.push(left);
-        right_numbers.push(right);
-    }
+    let mut left_numbers: Vec<isize> = pairs.iter().map(|p| p.left).collect();
+    let mut right_numbers: Vec<isize> = pairs.iter().map(|p| p.right).collect();
 
     left_numbers.sort();
     right_numbers.sort();
 
-    debug_assert!(left_numbers.len() == right_numbers.len());
+    Lists {
+        left: left_numbers,
+        right: right_numbers,
+    }
+}
 
+#[aoc(day = 1, part = 1)]
+fn solve_part_1(lists: &Lists) -> usize {
     let mut result: isize = 0;
-    for i in 0..left_numbers.len() {
-        result += (left_numbers[i] - right_numbers[i]).abs();
+    for i in 0..lists.left.len() {
+        result += (lists.left[i] - lists.right[i]).abs();
     }
 
-    // println!("Part 1 Result: {result}");
+    result as usize
+}
 
+#[aoc(day = 1, part = 2)]
+fn solve_part_2(lists: &Lists) -> usize {
     // Similarity score
     let mut similarity_score: usize = 0;
 
-    for value in left_numbers {
-        similarity_score += value as usize * right_numbers.iter().filter(|&n| *n == value).count();
+    for &value in &lists.left {
+        similarity_score += value as usize * lists.right.iter().filter(|&n| *n == value).count();
+    }
+
+    similarity_score
+}
+
+/// Same as part 2, counting the right numbers once instead of for each left one
+#[aoc(day = 1, part = 2, name = "counts")]
+fn solve_part_2_counts(lists: &Lists) -> usize {
+    let mut counts: HashMap<isize, usize> = HashMap::new();
+    for &value in &lists.right {
+        *counts.entry(value).or_default() += 1;
     }
-    // println!("Part 2 Result: {similarity_score}");
 
-    (Some(result as usize), Some(similarity_score))
+    lists
+        .left
+        .iter()
+        .map(|value| *value as usize * counts.get(value).unwrap_or(&0))
+        .sum()
 }
@@ -1,8 +1,6 @@
-use crate::input;
 use std::str::Split;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_2_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     // Problem 1
     let mut safe_count: usize = 0;
 
@@ -1,4 +1,3 @@
-use crate::input;
 use regex::Regex;
 
 const MUL_REGEX: &str = r#"mul\((?<X>\d{1,3}),(?<Y>\d+{1,3})\)"#;
@@ -8,9 +7,7 @@ enum Enablement {
     Dont,
 }
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_3_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let part_1_total = compute_multiplications(input);
     // println!("Part 1 Result: {part_1_total}");
 
@@ -1,9 +1,8 @@
+use aoc_utils::bit_grid::BitGrid;
+use aoc_utils::logging::debug;
 use std::collections::HashSet;
 
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_6_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let grid = Grid::from_str(input);
 
     let part_1_total = solve_part_1(&grid);
@@ -23,19 +22,10 @@ fn solve_part_1(grid: &Grid) -> usize {
 }
 
 fn solve_part_2(grid: &Grid) -> usize {
-    let area = grid.area();
-    // println!("The grid is {} squares", area);
+    debug!("The grid is {} squares", grid.area());
 
     let obstacle_positions = grid.get_char_positions('.');
-    /*
-    let g1 = grid.get_char_positions('^');
-    let o1 = grid.get_char_positions('#');
-     println!(
-        "There are {} possible obstacles ({} {})",
-        obstacle_positions.len(),
-        g1.len(),
-        o1.len()
-    );*/
+    debug!("There are {} possible obstacles", obstacle_positions.len());
 
     let mut stuck_count = 0;
     for (l, p) in obstacle_positions {
@@ -99,6 +89,11 @@ impl Grid {
         None
     }
 
+    /// Map of the tiles, with none visited yet
+    fn visited_map(&self) -> BitGrid {
+        BitGrid::new(self.data.first().map_or(0, Vec::len), self.data.len())
+    }
+
     fn area(&self) -> usize {
         let mut area = 0;
         for line in self.data.iter().by_ref() {
@@ -145,7 +140,7 @@ struct Guard {
     line: usize,
     pos: usize,
     dir: Direction,
-    visited: HashSet<(usize, usize)>,
+    visited: BitGrid,
     obstacles: HashSet<(usize, usize, Direction)>,
 }
 
@@ -154,8 +149,8 @@ impl Guard {
         let opt = grid.find_char('^');
         if opt.is_some() {
             let (line, pos) = opt.unwrap();
-            let mut visited = HashSet::new();
-            visited.insert((line, pos));
+            let mut visited = grid.visited_map();
+            visited.insert((pos, line));
             return Some(Self {
                 line,
                 pos,
@@ -167,8 +162,8 @@ impl Guard {
         let opt = grid.find_char('>');
         if opt.is_some() {
             let (line, pos) = opt.unwrap();
-            let mut visited = HashSet::new();
-            visited.insert((line, pos));
+            let mut visited = grid.visited_map();
+            visited.insert((pos, line));
             return Some(Self {
                 line,
                 pos,
@@ -180,8 +175,8 @@ impl Guard {
         let opt = grid.find_char('v');
         if opt.is_some() {
             let (line, pos) = opt.unwrap();
-            let mut visited = HashSet::new();
-            visited.insert((line, pos));
+            let mut visited = grid.visited_map();
+            visited.insert((pos, line));
             return Some(Self {
                 line,
                 pos,
@@ -193,8 +188,8 @@ impl Guard {
         let opt = grid.find_char('<');
         if opt.is_some() {
             let (line, pos) = opt.unwrap();
-            let mut visited = HashSet::new();
-            visited.insert((line, pos));
+            let mut visited = grid.visited_map();
+            visited.insert((pos, line));
             return Some(Self {
                 line,
                 pos,
@@ -237,7 +232,7 @@ impl Guard {
                 _ => {
                     self.line = l;
                     self.pos = p;
-                    self.visited.insert((self.line, self.pos));
+                    self.visited.insert((self.pos, self.line));
                     // Slick =)
                     Some(self.walk(grid)? + 1)
                 }
@@ -247,6 +242,6 @@ impl Guard {
     }
 
     fn covered_ground(&self) -> usize {
-        self.visited.len()
+        self.visited.count()
     }
 }
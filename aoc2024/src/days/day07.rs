@@ -1,7 +1,4 @@
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_7_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let part_1_total = solve_part_1(input);
     //println!("Part 1 Result: {part_1_total}");
 
@@ -1,8 +1,6 @@
-use crate::input;
 use std::collections::HashSet;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_8_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let grid = Grid::from_str(input);
 
     let part_1_total = solve_part_1(&grid);
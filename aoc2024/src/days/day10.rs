@@ -1,10 +1,10 @@
-use crate::input;
+use aoc_utils::grid::InvalidCell;
+use aoc_utils::Grid;
 use std::collections::HashSet;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_10_INPUT;
-
-    let grid = Grid::from_str(input);
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
+    let grid =
+        Grid::parse_with(input, |c| c.to_digit(10).ok_or(InvalidCell(c))).expect("Grid of digits");
 
     let part_1_total = solve_part_1(&grid);
     let part_2_total = solve_part_2(&grid);
@@ -12,138 +12,54 @@ pub fn solve() -> (Option<usize>, Option<usize>) {
     (part_1_total, part_2_total)
 }
 
-fn solve_part_1(grid: &Grid) -> Option<usize> {
-    let start_positions = grid.get_number_positions(0);
+fn solve_part_1(grid: &Grid<u32>) -> Option<usize> {
     let mut count = 0;
-    for (l, p) in start_positions {
-        count += grid
-            .find_trails_ends_from_position(l as isize, p as isize)
-            .len();
+    for (x, y) in grid.find_all(0) {
+        count += find_trails_ends_from_position(grid, (x, y)).len();
     }
     Some(count)
 }
 
-fn solve_part_2(grid: &Grid) -> Option<usize> {
-    let start_positions = grid.get_number_positions(0);
+fn solve_part_2(grid: &Grid<u32>) -> Option<usize> {
     let mut count = 0;
-    for (l, p) in start_positions {
-        count += grid.find_trails_from_position(l as isize, p as isize);
+    for (x, y) in grid.find_all(0) {
+        count += find_trails_from_position(grid, (x, y));
     }
     Some(count)
 }
 
-#[derive(Debug)]
-struct Grid {
-    data: Vec<Vec<u32>>,
-}
-
-impl Grid {
-    fn from_str(input: &str) -> Self {
-        let data: Vec<Vec<u32>> = input
-            .lines()
-            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-            .collect();
-        Self { data }
+fn find_trails_ends_from_position(
+    grid: &Grid<u32>,
+    position: (usize, usize),
+) -> HashSet<(usize, usize)> {
+    let current_gradient = grid[position];
+    let mut positions = HashSet::new();
+    if current_gradient == 9 {
+        positions.insert(position);
+        return positions;
     }
 
-    fn at(&self, line: isize, pos: isize) -> Option<u32> {
-        if line < 0 || pos < 0 {
-            return None;
+    for (_, next, &next_gradient) in grid.orthogonal_cells(position) {
+        if next_gradient == current_gradient + 1 {
+            positions.extend(find_trails_ends_from_position(grid, next.as_usize_tuple()));
         }
-        let line = line as usize;
-        let pos = pos as usize;
-        self.data.get(line)?.get(pos).copied()
-    }
-
-    // Not sure if I prefer actual for loops or this
-    fn get_number_positions(&self, v: u32) -> Vec<(usize, usize)> {
-        let mut positions = Vec::new();
-
-        self.data.iter().by_ref().enumerate().for_each(|(l, line)| {
-            line.iter()
-                .by_ref()
-                .enumerate()
-                .map(|(p, value)| {
-                    if *value == v {
-                        return Some((l, p));
-                    }
-                    None
-                })
-                .filter(|e| e.is_some())
-                .for_each(|e| positions.push(e.unwrap()));
-        });
-        positions
     }
 
-    fn find_trails_ends_from_position(&self, l: isize, p: isize) -> HashSet<(isize, isize)> {
-        let current_gradient = self.at(l, p);
-        if current_gradient.is_none() {
-            return HashSet::new();
-        }
-        let current_gradient = current_gradient.unwrap();
-        let mut positions = HashSet::new();
-        if current_gradient == 9 {
-            positions.insert((l, p));
-            return positions;
-        }
-
-        const DIRECTIONS: [(isize, isize); 4] = [
-            (0, 1),  // Right
-            (1, 0),  // Down
-            (0, -1), // Left
-            (-1, 0), // Up
-        ];
-        for (dl, dp) in &DIRECTIONS {
-            if let Some(next_gradient) = self.at(l + dl, p + dp) {
-                if next_gradient == current_gradient + 1 {
-                    positions.extend(self.find_trails_ends_from_position(l + dl, p + dp));
-                }
-            }
-        }
+    positions
+}
 
-        positions
+fn find_trails_from_position(grid: &Grid<u32>, position: (usize, usize)) -> usize {
+    let current_gradient = grid[position];
+    if current_gradient == 9 {
+        return 1;
     }
 
-    fn find_trails_from_position(&self, l: isize, p: isize) -> usize {
-        let current_gradient = self.at(l, p);
-        if current_gradient.is_none() {
-            return 0;
-        }
-        let current_gradient = current_gradient.unwrap();
-        if current_gradient == 9 {
-            return 1;
-        }
-
-        let mut count = 0;
-        const DIRECTIONS: [(isize, isize); 4] = [
-            (0, 1),  // Right
-            (1, 0),  // Down
-            (0, -1), // Left
-            (-1, 0), // Up
-        ];
-        for (dl, dp) in &DIRECTIONS {
-            if let Some(next_gradient) = self.at(l + dl, p + dp) {
-                if next_gradient == current_gradient + 1 {
-                    count += self.find_trails_from_position(l + dl, p + dp);
-                }
-            }
+    let mut count = 0;
+    for (_, next, &next_gradient) in grid.orthogonal_cells(position) {
+        if next_gradient == current_gradient + 1 {
+            count += find_trails_from_position(grid, next.as_usize_tuple());
         }
-
-        count
     }
-}
 
-impl std::fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut representation = String::new();
-        for line in self.data.iter() {
-            for value in line.iter() {
-                representation.push(char::from_digit(*value, 10).unwrap());
-            }
-            representation.push('\n');
-        }
-
-        write!(f, "{}", representation)?;
-        Ok(())
-    }
+    count
 }
@@ -1,12 +1,7 @@
-use crate::input;
-use regex::Regex;
+use aoc_macros::FromInput;
+use aoc_utils::parse;
 
-const BUTTON_A_REGEX: &str = r#"Button A: X\+(?<left>\d+), Y\+(?<right>\d+)"#;
-const BUTTON_B_REGEX: &str = r#"Button B: X\+(?<left>\d+), Y\+(?<right>\d+)"#;
-const PRIZE_REGEX: &str = r#"Prize: X=(?<left>\d+), Y=(?<right>\d+)"#;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_13_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let equations = Equations::from_str(input);
     let part_1_total = solve_part_1(&equations);
     let part_2_total = solve_part_2(&equations);
@@ -21,6 +16,18 @@ fn solve_part_2(equations: &Equations) -> Option<usize> {
     Some(equations.calculate_price_calibration())
 }
 
+/// A claw machine, as described in the input
+#[derive(Debug, FromInput)]
+#[input("Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}")]
+struct Machine {
+    ax: isize,
+    ay: isize,
+    bx: isize,
+    by: isize,
+    px: isize,
+    py: isize,
+}
+
 #[derive(Debug, Clone)]
 struct Equation {
     /// A (X1, Y1)
@@ -118,44 +125,15 @@ struct Equations {
 
 impl Equations {
     pub fn from_str(input: &str) -> Self {
-        let button_a_re = Regex::new(BUTTON_A_REGEX).unwrap();
-        let button_b_re = Regex::new(BUTTON_B_REGEX).unwrap();
-        let prize_re = Regex::new(PRIZE_REGEX).unwrap();
-
-        let mut equations: Vec<Equation> = Vec::new();
-        let mut a: Option<(isize, isize)> = None;
-        let mut b: Option<(isize, isize)> = None;
-
-        // Ugly parsing, there is probably much better
-        for line in input.lines() {
-            // println!("Line {}: {}", i, line);
-            if line.is_empty() {
-                continue;
-            }
-
-            if line.contains("Button A") {
-                let caps = button_a_re.captures(line).unwrap();
-                let x: isize = caps["right"].parse().unwrap();
-                let y: isize = caps["left"].parse().unwrap();
-                a = Some((x, y));
-            } else if line.contains("Button B") {
-                let caps = button_b_re.captures(line).unwrap();
-                let x: isize = caps["right"].parse().unwrap();
-                let y: isize = caps["left"].parse().unwrap();
-                b = Some((x, y))
-            } else if line.contains("Prize") {
-                let caps = prize_re.captures(line).unwrap();
-                let x: isize = caps["right"].parse().unwrap();
-                let y: isize = caps["left"].parse().unwrap();
-                equations.push(Equation {
-                    a: a.unwrap(),
-                    b: b.unwrap(),
-                    prize: (x, y),
-                });
-                a = None;
-                b = None;
-            }
-        }
+        let machines: Vec<Machine> = parse::blocks(input).expect("Invalid machines");
+        let equations = machines
+            .into_iter()
+            .map(|m| Equation {
+                a: (m.ax, m.ay),
+                b: (m.bx, m.by),
+                prize: (m.px, m.py),
+            })
+            .collect();
         Self { equations }
     }
 
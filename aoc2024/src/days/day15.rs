@@ -1,15 +1,29 @@
-use crate::input;
+use aoc_macros::GridCell;
 use aoc_utils::direction::Direction;
 use aoc_utils::grid::Grid;
 use aoc_utils::moves::Moves;
 
 use std::str::FromStr;
 
-type Map = Grid<char>;
+type Map = Grid<Tile>;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_15_INPUT;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('#')]
+    Wall,
+    #[cell('O')]
+    Box,
+    #[cell('[')]
+    BoxLeft,
+    #[cell(']')]
+    BoxRight,
+    #[cell('.')]
+    Empty,
+    #[cell('@')]
+    Robot,
+}
 
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let sections = input
         .split("\n\n")
         .filter(|s| !s.trim().is_empty())
@@ -31,12 +45,12 @@ pub fn solve() -> (Option<usize>, Option<usize>) {
 
 fn solve_part_1(mut grid: Map, moves: &[Moves]) -> Option<usize> {
     for move_list in moves.iter() {
-        let start = grid.find('@').expect("Grid contains @ symbol");
+        let start = grid.find(Tile::Robot).expect("Grid contains @ symbol");
         apply_moves_on_grid(&mut grid, start, &move_list);
     }
 
     let mut part_1_count = 0;
-    grid.find_all('O').for_each(|(x, y)| {
+    grid.find_all(Tile::Box).for_each(|(x, y)| {
         part_1_count += 100 * y + x;
     });
 
@@ -49,11 +63,11 @@ fn solve_part_2(original_grid: &Map, moves: &[Moves]) -> Option<usize> {
     let mut grid: Map = expand_grid(original_grid);
 
     for move_list in moves.iter() {
-        let start = grid.find('@').expect("Grid contains @ symbol");
+        let start = grid.find(Tile::Robot).expect("Grid contains @ symbol");
         apply_moves_on_grid(&mut grid, start, &move_list);
     }
     let mut part_2_count = 0;
-    grid.find_all('[').for_each(|(x, y)| {
+    grid.find_all(Tile::BoxLeft).for_each(|(x, y)| {
         part_2_count += 100 * y + x;
     });
 
@@ -95,18 +109,18 @@ fn move_on_grid(
     //println!("Recursing at position {:?}", start);
     let destination = direction.move_point(start);
     match grid[destination] {
-        'O' => {
+        Tile::Box => {
             //Try to move the box ahead, then ourselves
             let _ = move_on_grid(grid, destination, direction)?;
             move_on_grid(grid, start, direction)
             //panic!("Danger");
         }
-        '.' => {
+        Tile::Empty => {
             // Update all the items to move, start by moving the last
             // one by one, and then the others
             //println!("Moving to empty spot");
             grid[destination] = grid[start];
-            grid[start] = '.';
+            grid[start] = Tile::Empty;
             Some(destination)
         }
         _ => {
@@ -127,13 +141,13 @@ fn move_on_grid_unchecked(
     //println!("Recursing at position {:?}", start);
     let destination = direction.move_point(start);
     match grid[destination] {
-        'O' => {
+        Tile::Box => {
             //Try to move the box ahead, then ourselves
             move_on_grid_unchecked(grid, destination, direction);
             move_on_grid_unchecked(grid, start, direction)
             //panic!("Danger");
         }
-        '[' => {
+        Tile::BoxLeft => {
             move_on_grid_unchecked(grid, destination, direction);
             let new_start = move_on_grid_unchecked(grid, start, direction);
             if direction == Direction::Up || direction == Direction::Down {
@@ -142,7 +156,7 @@ fn move_on_grid_unchecked(
             }
             new_start
         }
-        ']' => {
+        Tile::BoxRight => {
             move_on_grid_unchecked(grid, destination, direction);
             let new_start = move_on_grid_unchecked(grid, start, direction);
             if direction == Direction::Up || direction == Direction::Down {
@@ -151,11 +165,11 @@ fn move_on_grid_unchecked(
             }
             new_start
         }
-        '.' => {
+        Tile::Empty => {
             // Update all the items to move, start by moving the last
             // one by one, and then the others
             grid[destination] = grid[start];
-            grid[start] = '.';
+            grid[start] = Tile::Empty;
             destination
         }
         c => {
@@ -173,14 +187,14 @@ fn is_move_possible(grid: &Map, start: (usize, usize), direction: Direction) ->
     //println!("Recursing at position {:?}", start);
     let destination = direction.move_point(start);
     match grid[destination] {
-        'O' => {
+        Tile::Box => {
             //Try to move the box ahead, then ourselves
             if !is_move_possible(grid, destination, direction) {
                 return false;
             }
             true
         }
-        '[' => {
+        Tile::BoxLeft => {
             // Both this side and the other side of the box must be
             // movable for it to be pushed
             if !is_move_possible(grid, destination, direction) {
@@ -200,7 +214,7 @@ fn is_move_possible(grid: &Map, start: (usize, usize), direction: Direction) ->
 
             true
         }
-        ']' => {
+        Tile::BoxRight => {
             // Both this side and the other side of the box must be
             // movable for it to be pushed
             if !is_move_possible(grid, destination, direction) {
@@ -219,7 +233,7 @@ fn is_move_possible(grid: &Map, start: (usize, usize), direction: Direction) ->
 
             true
         }
-        '.' => {
+        Tile::Empty => {
             // Update all the items to move, start by moving the last
             // one by one, and then the others
             //println!("Moving to empty spot");
@@ -241,21 +255,21 @@ fn expand_grid(grid: &Map) -> Map {
     // There may be a way to do this without pre-allocating(like iter/zip/unzip/collect?)
     // but this works too
     grid.data.iter().for_each(|c| match *c {
-        '#' => {
-            expanded.push('#');
-            expanded.push('#');
+        Tile::Wall => {
+            expanded.push(Tile::Wall);
+            expanded.push(Tile::Wall);
         }
-        'O' => {
-            expanded.push('[');
-            expanded.push(']');
+        Tile::Box => {
+            expanded.push(Tile::BoxLeft);
+            expanded.push(Tile::BoxRight);
         }
-        '.' => {
-            expanded.push('.');
-            expanded.push('.');
+        Tile::Empty => {
+            expanded.push(Tile::Empty);
+            expanded.push(Tile::Empty);
         }
-        '@' => {
-            expanded.push('@');
-            expanded.push('.');
+        Tile::Robot => {
+            expanded.push(Tile::Robot);
+            expanded.push(Tile::Empty);
         }
         _ => panic!("Unexpected character in grid: {}", c),
     });
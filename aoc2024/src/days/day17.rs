@@ -1,10 +1,8 @@
+use aoc_macros::scan;
+use aoc_utils::logging::debug;
 use itertools::Itertools;
 
-use crate::input;
-
-pub fn solve() -> (Option<String>, Option<usize>) {
-    let input = input::DAY_17_INPUT;
-
+pub fn solve(input: &str) -> (Option<String>, Option<usize>) {
     let part_1_total = solve_part_1(input);
     let part_2_total = solve_part_2(input);
 
@@ -90,17 +88,17 @@ impl Computer {
                 continue;
             }
 
-            if line.contains("Register A:") {
-                let (_, number) = line.split_once("Register A:").unwrap();
-                register_a = number.trim().parse().unwrap();
-            } else if line.contains("Register B:") {
-                let (_, number) = line.split_once("Register B:").unwrap();
-                register_b = number.trim().parse().unwrap();
-            } else if line.contains("Register C:") {
-                let (_, number) = line.split_once("Register C:").unwrap();
-                register_c = number.trim().parse().unwrap();
+            if line.starts_with("Register") {
+                let (register, value) =
+                    scan!("Register {}: {}", line => char, usize).expect("Invalid register");
+                match register {
+                    'A' => register_a = value,
+                    'B' => register_b = value,
+                    'C' => register_c = value,
+                    _ => panic!("Unknown register {}", register),
+                }
             } else {
-                let (_, instructions) = line.split_once("Program:").unwrap();
+                let instructions = scan!("Program: {}", line => String).expect("Invalid program");
 
                 // Program is comma separater opcodes/operand pairs
                 let mut instructions = instructions.split(",");
@@ -135,7 +133,7 @@ impl Computer {
     /// 7: If A != 0 {JMP 0};
     ///
     fn print_program(self) {
-        println!("Program: ");
+        debug!("Program:");
         for (opcode, operand) in self.program.iter() {
             let operand_string: String = match *operand {
                 c if (0..=3).contains(&c) => c.to_string(),
@@ -146,17 +144,16 @@ impl Computer {
             };
 
             match opcode {
-                OpCodes::Adv => println!("A = A >> {}", operand_string),
-                OpCodes::Bxl => println!("B = B ^ {}", operand),
-                OpCodes::Bst => println!("B = {} % 8", operand_string),
-                OpCodes::Jnz => println!("If A != 0 {{JMP {}}}", operand),
-                OpCodes::Bxc => println!("B = B ^ C"),
-                OpCodes::Out => println!("Print {} % 8", operand_string),
-                OpCodes::Bdv => println!("B = A >> {}", operand_string),
-                OpCodes::Cdv => println!("C = A >> {}", operand_string),
+                OpCodes::Adv => debug!("A = A >> {}", operand_string),
+                OpCodes::Bxl => debug!("B = B ^ {}", operand),
+                OpCodes::Bst => debug!("B = {} % 8", operand_string),
+                OpCodes::Jnz => debug!("If A != 0 {{JMP {}}}", operand),
+                OpCodes::Bxc => debug!("B = B ^ C"),
+                OpCodes::Out => debug!("Print {} % 8", operand_string),
+                OpCodes::Bdv => debug!("B = A >> {}", operand_string),
+                OpCodes::Cdv => debug!("C = A >> {}", operand_string),
             }
         }
-        println!("");
     }
 
     /// Runs the program and returns its output
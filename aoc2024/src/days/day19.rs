@@ -1,12 +1,11 @@
-use crate::input;
+use aoc_utils::logging::debug;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_19_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let patterns = Patterns::from_str(input.lines().next().expect("File is not empty"));
     let designs = Designs::from_str(input);
-    println!(
+    debug!(
         "Patterns: {:?}, Designs: {:?}",
         patterns.patterns.len(),
         designs.designs.len()
@@ -106,63 +105,18 @@ impl Designs {
     pub fn count_all_designs(&self, patterns: &Patterns) -> usize {
         let mut count = 0;
 
+        // The designs often end the same way, so the counts are cached. The
+        // cache is only valid for these patterns
+        let mut cache = HashMap::new();
         self.designs.iter().for_each(|design| {
             // print!("Design: {:?} - ", design);
-            let mut visited: HashMap<String, usize> = HashMap::new();
-            count += Self::count_design_combinations(design, design, &mut visited, patterns);
+            count += count_design_combinations(design, patterns, &mut cache);
             // println!("Design: {:?} - Count: {:?}", design, count);
-            // println!("Visited: {:?} ", visited);
         });
 
         count
     }
 
-    fn count_design_combinations(
-        design: &str,
-        remaining: &str,
-        visited: &mut HashMap<String, usize>,
-        patterns: &Patterns,
-    ) -> usize {
-        // Current is the start of design minus remaining
-        let current = String::from(&design[..design.len() - remaining.len()]);
-        // println!("Current: {:?} - Remaining: {:?}", current, remaining);
-
-        if let Some(&cached_result) = visited.get(&current) {
-            return cached_result;
-        }
-
-        let mut count = 0;
-        let mut node = &patterns.trie;
-
-        for (i, c) in remaining.chars().enumerate() {
-            match node.children.get(&c) {
-                Some(child) => {
-                    node = child;
-
-                    if node.is_end {
-                        let new_remaining = &remaining[i + 1..];
-                        if new_remaining.is_empty() {
-                            count += 1;
-                        } else {
-                            count += Self::count_design_combinations(
-                                design,
-                                new_remaining,
-                                visited,
-                                patterns,
-                            );
-                        }
-                    }
-                }
-                None => {
-                    break;
-                }
-            }
-        }
-
-        visited.insert(current, count);
-        count
-    }
-
     fn is_design_possible(
         design: &str,
         current: Option<&str>,
@@ -199,3 +153,40 @@ impl Designs {
         false
     }
 }
+
+/// Number of ways to make the remaining part of a design with the patterns.
+/// The cache keeps the counts of the endings already seen with these patterns
+fn count_design_combinations<'a>(
+    remaining: &'a str,
+    patterns: &Patterns,
+    cache: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(&count) = cache.get(remaining) {
+        return count;
+    }
+    let mut count = 0;
+    let mut node = &patterns.trie;
+
+    for (i, c) in remaining.chars().enumerate() {
+        match node.children.get(&c) {
+            Some(child) => {
+                node = child;
+
+                if node.is_end {
+                    let new_remaining = &remaining[i + 1..];
+                    if new_remaining.is_empty() {
+                        count += 1;
+                    } else {
+                        count += count_design_combinations(new_remaining, patterns, cache);
+                    }
+                }
+            }
+            None => {
+                break;
+            }
+        }
+    }
+
+    cache.insert(remaining, count);
+    count
+}
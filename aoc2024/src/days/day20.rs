@@ -1,8 +1,4 @@
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_20_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let part_1_total = solve_part_1(input);
     let part_2_total = solve_part_2(input);
 
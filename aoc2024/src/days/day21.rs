@@ -1,8 +1,8 @@
-use crate::input;
+use aoc_macros::memoize;
+use aoc_utils::logging::{debug, trace};
 use itertools::Itertools;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_21_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let codes = Codes::from_str(input);
 
     let part_1_total = solve_part_1(&codes);
@@ -16,8 +16,7 @@ fn solve_part_1(codes: &Codes) -> Option<usize> {
 }
 
 fn solve_part_2(codes: &Codes) -> Option<usize> {
-    None
-    //Some(codes.solve_part_2())
+    Some(codes.solve_part_2())
 }
 
 #[derive(Debug)]
@@ -76,22 +75,19 @@ impl<'a> Codes<'a> {
     pub fn solve(&self) -> usize {
         let mut total = 0;
         for code in &self.codes {
-            println!("-----------------------------------");
-            println!("Solving Code: {}", code);
+            debug!("Solving Code: {}", code);
             let first_codes = Self::solve_keypad(&Self::NUM_KEYPAD, code);
 
             let mut final_codes = Self::iterate_keypad(&Self::ARROW_KEYPAD, first_codes, 2);
 
             // Update the total
-            for s in &final_codes {
-                // println!("Solution: {}", s);
-            }
-            println!("number of solutions : {}", final_codes.len());
+            trace!("Solutions: {:?}", final_codes);
+            debug!("number of solutions : {}", final_codes.len());
             let solution = final_codes.swap_remove(0);
             let mut code_num = String::from(*code);
             code_num = code_num.replace("A", "");
             let code_num = code_num.parse::<usize>().unwrap();
-            println!("Solution: {} - {}", code_num, solution.len());
+            debug!("Solution: {} - {}", code_num, solution.len());
 
             total += code_num * solution.len();
         }
@@ -100,30 +96,52 @@ impl<'a> Codes<'a> {
     }
 
     pub fn solve_part_2(&self) -> usize {
+        self.solve_with_robots(25)
+    }
+
+    /// Same as `solve`, with any number of robots on arrow keypads. Only the
+    /// number of presses is computed, the sequences get far too long
+    pub fn solve_with_robots(&self, robots: usize) -> usize {
         let mut total = 0;
         for code in &self.codes {
-            println!("-----------------------------------");
-            println!("Solving Code: {}", code);
-            let first_codes = Self::solve_keypad(&Self::NUM_KEYPAD, code);
-
-            let mut final_codes = Self::iterate_keypad(&Self::ARROW_KEYPAD, first_codes, 25);
-
-            // Update the total
-            println!("number of solutions : {}", final_codes.len());
-            let solution = final_codes.swap_remove(0);
-            let mut code_num = String::from(*code);
-            code_num = code_num.replace("A", "");
-            let code_num = code_num.parse::<usize>().unwrap();
-            println!("Solution: {} - {}", code_num, solution.len());
+            debug!("Solving Code: {}", code);
+            let mut position = Self::find_char(&Self::NUM_KEYPAD, 'A').unwrap();
+            let mut length = 0;
+            for key in code.chars() {
+                let target = Self::find_char(&Self::NUM_KEYPAD, key).unwrap();
+                length += Self::generate_paths(&Self::NUM_KEYPAD, position, target)
+                    .iter()
+                    .map(|path| Self::sequence_presses(path, robots))
+                    .min()
+                    .unwrap();
+                position = target;
+            }
 
-            total += code_num * solution.len();
+            let code_num = code.replace("A", "").parse::<usize>().unwrap();
+            debug!("Solution: {} - {}", code_num, length);
+            total += code_num * length;
         }
 
         total
     }
 
+    /// Presses on our keypad to have the moves of a path, followed by `A`,
+    /// typed on a keypad with `robots` arrow keypads in between
+    fn sequence_presses(path: &[char], robots: usize) -> usize {
+        if robots == 0 {
+            return path.len() + 1;
+        }
+        let mut from = 'A';
+        let mut presses = 0;
+        for &to in path.iter().chain(std::iter::once(&'A')) {
+            presses += arrow_presses(from, to, robots);
+            from = to;
+        }
+        presses
+    }
+
     pub fn iterate_keypad(keypad: &[&[char]], codes: Vec<String>, i: usize) -> Vec<String> {
-        println!(
+        trace!(
             "Iterating keypad: {} - size of the codes {}",
             i,
             codes.len()
@@ -251,3 +269,16 @@ impl<'a> Codes<'a> {
         valid_paths
     }
 }
+
+/// Presses on our keypad to move from a key to another on an arrow keypad and
+/// press it, with `robots` arrow keypads between ours and that one included
+#[memoize]
+fn arrow_presses(from: char, to: char, robots: usize) -> usize {
+    let from = Codes::find_char(&Codes::ARROW_KEYPAD, from).unwrap();
+    let to = Codes::find_char(&Codes::ARROW_KEYPAD, to).unwrap();
+    Codes::generate_paths(&Codes::ARROW_KEYPAD, from, to)
+        .iter()
+        .map(|path| Codes::sequence_presses(path, robots - 1))
+        .min()
+        .unwrap()
+}
@@ -1,24 +0,0 @@
-pub const DAY_1_INPUT: &str = include_str!("day01.txt");
-pub const DAY_2_INPUT: &str = include_str!("day02.txt");
-pub const DAY_3_INPUT: &str = include_str!("day03.txt");
-pub const DAY_4_INPUT: &str = include_str!("day04.txt");
-pub const DAY_5_INPUT: &str = include_str!("day05.txt");
-pub const DAY_6_INPUT: &str = include_str!("day06.txt");
-pub const DAY_7_INPUT: &str = include_str!("day07.txt");
-pub const DAY_8_INPUT: &str = include_str!("day08.txt");
-pub const DAY_9_INPUT: &str = include_str!("day09.txt");
-pub const DAY_10_INPUT: &str = include_str!("day10.txt");
-pub const DAY_11_INPUT: &str = include_str!("day11.txt");
-pub const DAY_12_INPUT: &str = include_str!("day12.txt");
-pub const DAY_13_INPUT: &str = include_str!("day13.txt");
-pub const DAY_14_INPUT: &str = include_str!("day14.txt");
-pub const DAY_15_INPUT: &str = include_str!("day15.txt");
-pub const DAY_16_INPUT: &str = include_str!("day16.txt");
-pub const DAY_17_INPUT: &str = include_str!("day17.txt");
-pub const DAY_18_INPUT: &str = include_str!("day18.txt");
-pub const DAY_19_INPUT: &str = include_str!("day19.txt");
-pub const DAY_20_INPUT: &str = include_str!("day20.txt");
-pub const DAY_21_INPUT: &str = include_str!("day21-t.txt");
-pub const DAY_22_INPUT: &str = include_str!("day22.txt");
-pub const DAY_23_INPUT: &str = include_str!("day23.txt");
-pub const DAY_24_INPUT: &str = include_str!("day24.txt");
@@ -1,2 +1,88 @@
 pub mod days;
-pub mod input;
+
+use aoc_utils::registry;
+pub use aoc_utils::registry::Solution;
+pub use aoc_utils::solution::{Answer, Part, SolveError};
+
+/// Solves one part of a day on any input, without going through the input
+/// files:
+///
+/// ```rust,no_run
+/// use aoc2024::Part;
+///
+/// let input = std::fs::read_to_string("input.txt").unwrap();
+/// match aoc2024::run(1, Part::One, &input) {
+///     Ok(answer) => println!("{}", answer),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+///
+/// Panics in the solution are returned as errors, but the panic hook still
+/// prints their message.
+pub fn run(day: usize, part: Part, input: &str) -> Result<Answer, SolveError> {
+    aoc_macros::run_day!(day, part, input)
+}
+
+/// The solutions registered with `#[aoc]` in this crate, including the
+/// alternative implementations
+pub fn solutions() -> Vec<&'static Solution> {
+    registry::solutions(env!("CARGO_CRATE_NAME"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        assert_eq!(run(1, Part::One, input), Ok(Answer::Number(11)));
+        assert_eq!(run(1, Part::Two, input), Ok(Answer::Number(31)));
+        assert_eq!(run(26, Part::One, input), Err(SolveError::UnknownDay(26)));
+        assert_eq!(
+            run(24, Part::One, input),
+            Err(SolveError::NotImplemented(24, Part::One))
+        );
+    }
+
+    #[test]
+    // A missing input is reported with a deprecation warning
+    #[allow(deprecated)]
+    fn test_include_input() {
+        use aoc_macros::include_input;
+        use aoc_utils::input::{EmbeddedInput, InputError};
+
+        // The inputs are not committed, this one is made for the test
+        let present: EmbeddedInput = include_input!("fixtures/embedded.txt");
+        assert_eq!(present.get().unwrap(), "embedded input\n");
+
+        let missing: EmbeddedInput = include_input!("input/day26.txt");
+        assert!(matches!(
+            missing.get(),
+            Err(InputError::Missing(path)) if path.ends_with("src/input/day26.txt")
+        ));
+    }
+
+    #[test]
+    fn test_from_input_names() {
+        use aoc_macros::FromInput;
+
+        // Fields named like the generated locals, and a raw identifier
+        #[derive(Debug, PartialEq, FromInput)]
+        #[input("{scanner} is a {type}")]
+        struct Item {
+            scanner: String,
+            r#type: String,
+        }
+
+        let item: Item = "door is a gate".parse().unwrap();
+        assert_eq!(
+            item,
+            Item {
+                scanner: "door".to_string(),
+                r#type: "gate".to_string(),
+            }
+        );
+    }
+}
@@ -1,5 +1,3 @@
-use crate::input;
-
 const MAX_ANGLE: isize = 100;
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -57,9 +55,7 @@ impl Dial {
     }
 }
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_1_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let mut movements = Vec::new();
     for line in input.lines() {
         if line.is_empty() {
@@ -1,7 +1,4 @@
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_2_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let input = input.trim();
 
     let mut ranges: Vec<(usize, usize)> = Vec::new();
@@ -1,9 +1,6 @@
-use crate::input;
-
 type Batteries = Vec<Vec<usize>>;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_3_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let input = input.trim();
 
     let mut batteries: Batteries = Vec::new();
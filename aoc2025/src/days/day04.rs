@@ -1,11 +1,8 @@
 use std::str::FromStr;
 
-use crate::input;
 use aoc_utils::Grid;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_4_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let mut grid: Grid<char> = Grid::from_str(input).expect("valid grid");
 
     let part_1_total = solve_part_1(&grid);
@@ -18,12 +15,10 @@ fn solve_part_1(grid: &Grid<char>) -> Option<usize> {
     let mut forklift_rolls = 0;
     for roll in grid.find_all('@') {
         // Find adjacent
-        let mut roll_count = 0;
-        for neighbor in grid.neighbors(roll) {
-            if grid[neighbor] == '@' {
-                roll_count += 1;
-            }
-        }
+        let roll_count = grid
+            .neighbor_cells(roll)
+            .filter(|&(_, _, &c)| c == '@')
+            .count();
 
         if roll_count < 4 {
             forklift_rolls += 1;
@@ -44,12 +39,10 @@ fn solve_part_2(grid: &mut Grid<char>) -> Option<usize> {
         roll_removed = false;
         for roll in grid.find_all('@') {
             // Find adjacent
-            let mut roll_count = 0;
-            for neighbor in grid.neighbors(roll) {
-                if grid[neighbor] == '@' {
-                    roll_count += 1;
-                }
-            }
+            let roll_count = grid
+                .neighbor_cells(roll)
+                .filter(|&(_, _, &c)| c == '@')
+                .count();
 
             if roll_count < 4 {
                 to_remove.push(roll);
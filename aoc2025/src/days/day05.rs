@@ -1,8 +1,4 @@
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_5_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let mut fresh_ingredients = Vec::new();
     let mut ingredients = Vec::new();
 
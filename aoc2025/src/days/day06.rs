@@ -1,8 +1,7 @@
-use crate::input;
-
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_6_INPUT;
+use aoc_utils::Grid;
+use std::str::FromStr;
 
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     let mut numbers = Vec::new();
     let mut operands = Vec::new();
 
@@ -64,48 +63,38 @@ fn solve_part_1(numbers: &[Vec<usize>], operands: &[char]) -> Option<usize> {
 }
 
 fn solve_part_2(input: &str, operands: &[char]) -> Option<usize> {
-    let mut total = 0;
-    let mut col_offset: usize = 0;
-
-    for operand in operands.iter() {
-        let mut local_sum = match operand {
-            '+' => 0,
-            '*' => 1,
-            c => panic!("Bad operand: {:?}", c),
-        };
-        loop {
-            let mut vertical_number = String::new();
-            for line in input.lines() {
-                if line.contains('+') {
-                    continue;
-                }
-                let line = line.as_bytes();
-                if col_offset >= line.len() {
-                    break;
-                }
-                match line[col_offset] {
-                    c if c.is_ascii_digit() => vertical_number.push(char::from(c)),
-                    _ => {}
-                }
-            }
-
-            if vertical_number.is_empty() {
-                col_offset += 1;
-                break;
+    // Numbers are written top to bottom, problems are separated by empty columns
+    let rows: Vec<&str> = input
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.contains(['+', '*']))
+        .collect();
+    let width = rows.iter().map(|row| row.len()).max()?;
+    let padded: String = rows
+        .iter()
+        .map(|row| format!("{:<width$}\n", row, width = width))
+        .collect();
+    let grid: Grid<char> = Grid::from_str(&padded).expect("correct input");
+
+    let mut problems: Vec<Vec<usize>> = vec![Vec::new()];
+    for column in grid.columns() {
+        let vertical_number: String = column.filter(|c| c.is_ascii_digit()).collect();
+        if vertical_number.is_empty() {
+            if problems.last().is_some_and(|numbers| !numbers.is_empty()) {
+                problems.push(Vec::new());
             }
-
-            let number = vertical_number.parse::<usize>().expect("correct number");
-
-            match operand {
-                '+' => local_sum += number,
-                '*' => local_sum *= number,
-                c => panic!("Bad operand: {:?}", c),
-            };
-            col_offset += 1;
+            continue;
         }
+        let number = vertical_number.parse::<usize>().expect("correct number");
+        problems.last_mut()?.push(number);
+    }
 
-        // Find highest digit in the collection
-        total += local_sum;
+    let mut total = 0;
+    for (numbers, operand) in problems.iter().zip(operands) {
+        total += match operand {
+            '+' => numbers.iter().sum::<usize>(),
+            '*' => numbers.iter().product(),
+            c => panic!("Bad operand: {:?}", c),
+        };
     }
     Some(total)
 }
@@ -1,21 +1,28 @@
-use std::{collections::HashMap, collections::HashSet, str::FromStr};
+use std::{collections::HashMap, collections::HashSet};
 
-use crate::input;
+use aoc_macros::GridCell;
 use aoc_utils::Grid;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_7_INPUT;
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
+    let (grid, positions) = Grid::parse_with_positions(input, &['S'], Tile::Empty, Tile::try_from)
+        .expect("Grid-like input");
+    let start = positions[&'S'];
 
-    let grid: Grid<char> = Grid::from_str(input).expect("Grid-like input");
-
-    let part_1_total = solve_part_1(&grid);
-    let part_2_total = solve_part_2(&grid);
+    let part_1_total = solve_part_1(&grid, start);
+    let part_2_total = solve_part_2(&grid, start);
 
     (part_1_total, part_2_total)
 }
 
-fn solve_part_1(grid: &Grid<char>) -> Option<usize> {
-    let start = grid.find('S').expect("S in input");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('.')]
+    Empty,
+    #[cell('^')]
+    Splitter,
+}
+
+fn solve_part_1(grid: &Grid<Tile>, start: (usize, usize)) -> Option<usize> {
     let mut beams = HashSet::new();
     beams.insert(start);
     let mut split_count = 0;
@@ -28,7 +35,7 @@ fn solve_part_1(grid: &Grid<char>) -> Option<usize> {
             if !grid.usize_point_within_bounds(down) {
                 break 'main;
             }
-            if grid[down] == '^' {
+            if grid[down] == Tile::Splitter {
                 let right = (down.0 + 1, down.1);
                 if grid.usize_point_within_bounds(right) {
                     new_beams.insert(right);
@@ -49,8 +56,7 @@ fn solve_part_1(grid: &Grid<char>) -> Option<usize> {
     Some(split_count)
 }
 
-fn solve_part_2(grid: &Grid<char>) -> Option<usize> {
-    let start = grid.find('S').expect("S in input");
+fn solve_part_2(grid: &Grid<Tile>, start: (usize, usize)) -> Option<usize> {
     let mut beams = HashSet::new();
     // Stores how many paths could have lead to a given beam
     let mut beams_power = HashMap::new();
@@ -68,7 +74,7 @@ fn solve_part_2(grid: &Grid<char>) -> Option<usize> {
             let parent_power = *beams_power.get(&beam).unwrap();
             assert!(parent_power > 0);
 
-            if grid[down] == '^' {
+            if grid[down] == Tile::Splitter {
                 let right = (down.0 + 1, down.1);
                 if grid.usize_point_within_bounds(right) {
                     let power = if let Some(&p) = beams_power.get(&right) {
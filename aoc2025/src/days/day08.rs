@@ -1,39 +1,15 @@
 // use std::collections::HashMap;
 
-use crate::input;
+use aoc_macros::FromInput;
+use aoc_utils::logging::debug;
+use aoc_utils::parse;
 
 const PART_1_CONNECTIONS: usize = 1000;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_8_INPUT;
-
+pub fn solve(input: &str) -> (Option<usize>, Option<usize>) {
     // Parse input into boxes
-    let mut boxes: Vec<(usize, usize, usize)> = Vec::new();
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        let mut split = line.split(',');
-        let x = split
-            .next()
-            .expect("Valid x coordinate")
-            .parse::<usize>()
-            .expect("Valid digit");
-        let y = split
-            .next()
-            .expect("Valid y coordinate")
-            .parse::<usize>()
-            .expect("Valid digit");
-        let z = split
-            .next()
-            .expect("Valid z coordinate")
-            .parse::<usize>()
-            .expect("Valid digit");
-
-        boxes.push((x, y, z));
-    }
+    let boxes: Vec<JunctionBox> = parse::lines(input).expect("Invalid junction boxes");
+    let boxes: Vec<(usize, usize, usize)> = boxes.iter().map(|b| (b.x, b.y, b.z)).collect();
 
     // Compute the distances of all pairs
     let mut distances = Vec::new();
@@ -49,9 +25,15 @@ pub fn solve() -> (Option<usize>, Option<usize>) {
     // Sort by smallest distance
     distances.sort_unstable_by_key(|(_, _, d)| *d);
 
-    // eprintln!( "Distance 1 : {:?} -> {:?} = {:?}", boxes[distances[0].0], boxes[distances[0].1], distances[0].2 );
-    // eprintln!( "Distance 2 : {:?} -> {:?} = {:?}", boxes[distances[1].0], boxes[distances[1].1], distances[1].2 );
-    // eprintln!( "Distance 3 : {:?} -> {:?} = {:?}", boxes[distances[2].0], boxes[distances[2].1], distances[2].2 );
+    for (n, &(i, j, d)) in distances.iter().take(3).enumerate() {
+        debug!(
+            "Distance {} : {:?} -> {:?} = {:?}",
+            n + 1,
+            boxes[i],
+            boxes[j],
+            d
+        );
+    }
 
     // Create a vec containing all the circuits
     let mut circuits: Vec<Vec<usize>> = Vec::with_capacity(boxes.len());
@@ -168,6 +150,15 @@ pub fn solve() -> (Option<usize>, Option<usize>) {
     (Some(part_1_total), part_2_total)
 }
 
+/// Position of a junction box, as given in the input
+#[derive(FromInput)]
+#[input("{x},{y},{z}")]
+struct JunctionBox {
+    x: usize,
+    y: usize,
+    z: usize,
+}
+
 /// We don't really need to square root, as this function is monotonic and will
 /// give us the same ordering as if we don't apply it.
 fn calculate_distance(lhs: &(usize, usize, usize), rhs: &(usize, usize, usize)) -> usize {
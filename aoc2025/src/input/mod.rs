@@ -1,12 +0,0 @@
-pub const DAY_1_INPUT: &str = include_str!("day01.txt");
-pub const DAY_2_INPUT: &str = include_str!("day02.txt");
-pub const DAY_3_INPUT: &str = include_str!("day03.txt");
-pub const DAY_4_INPUT: &str = include_str!("day04.txt");
-pub const DAY_5_INPUT: &str = include_str!("day05.txt");
-pub const DAY_6_INPUT: &str = include_str!("day06.txt");
-pub const DAY_7_INPUT: &str = include_str!("day07.txt");
-pub const DAY_8_INPUT: &str = include_str!("day08.txt");
-pub const DAY_9_INPUT: &str = include_str!("day09.txt");
-pub const DAY_10_INPUT: &str = include_str!("day10.txt");
-// pub const DAY_11_INPUT: &str = include_str!("day11.txt");
-// pub const DAY_12_INPUT: &str = include_str!("day12.txt");
@@ -1,2 +1,30 @@
 pub mod days;
-pub mod input;
+
+use aoc_utils::registry;
+pub use aoc_utils::registry::Solution;
+pub use aoc_utils::solution::{Answer, Part, SolveError};
+
+/// Solves one part of a day on any input, without going through the input
+/// files:
+///
+/// ```rust,no_run
+/// use aoc2025::Part;
+///
+/// let input = std::fs::read_to_string("input.txt").unwrap();
+/// match aoc2025::run(1, Part::One, &input) {
+///     Ok(answer) => println!("{}", answer),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+///
+/// Panics in the solution are returned as errors, but the panic hook still
+/// prints their message.
+pub fn run(day: usize, part: Part, input: &str) -> Result<Answer, SolveError> {
+    aoc_macros::run_day!(day, part, input)
+}
+
+/// The solutions registered with `#[aoc]` in this crate, including the
+/// alternative implementations
+pub fn solutions() -> Vec<&'static Solution> {
+    registry::solutions(env!("CARGO_CRATE_NAME"))
+}
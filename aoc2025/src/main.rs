@@ -1,19 +1,36 @@
+use std::process::ExitCode;
+
 use aoc2025::days;
 use aoc_macros::solve_days;
+use aoc_utils::config::{Config, ConfigArgs};
+use aoc_utils::runner::DaysArgs;
+use aoc_utils::Runner;
 use clap::Parser;
 
+/// Solves the Advent of Code 2025 puzzles
 #[derive(Parser, Debug)]
 struct Args {
-    #[arg(short, long)]
-    all: bool,
+    #[command(flatten)]
+    days: DaysArgs,
 
-    #[arg(long, short)]
-    days: Vec<usize>,
+    #[command(flatten)]
+    config: ConfigArgs,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Args::parse();
+    let config = match Config::load(&args.config) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    aoc_utils::logging::init(&config);
+    let mut runner = Runner::new(2025, config).with_solutions(aoc2025::solutions());
 
     // Solve the requested days in argument
-    solve_days!(1, 8, args);
+    solve_days!(args.days, runner);
+
+    runner.exit_code()
 }
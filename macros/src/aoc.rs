@@ -0,0 +1,146 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, FnArg, Ident, ItemFn, Lit, MetaNameValue, Token, Type};
+
+/// Arguments of the `#[aoc]` and `#[aoc_parser]` attributes:
+/// `day = 5, part = 2, name = "fast"`
+/// day is an integer, in the range 1..=25
+/// part is 1 or 2, only for `#[aoc]`
+/// name is an optional string, naming an alternative implementation
+///
+pub struct AocArgs {
+    pub day: usize,
+    pub part: Option<usize>,
+    pub name: Option<String>,
+}
+
+impl Parse for AocArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut day = None;
+        let mut part = None;
+        let mut name = None;
+
+        for arg in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let key = arg
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            let Expr::Lit(ExprLit { lit, .. }) = &arg.value else {
+                return Err(syn::Error::new_spanned(&arg.value, "expected a literal"));
+            };
+            match (key.as_str(), lit) {
+                ("day", Lit::Int(i)) => match i.base10_parse::<usize>()? {
+                    d @ 1..=25 => day = Some(d),
+                    _ => return Err(syn::Error::new_spanned(i, "days go from 1 to 25")),
+                },
+                ("part", Lit::Int(i)) => match i.base10_parse::<usize>()? {
+                    p @ 1..=2 => part = Some(p),
+                    _ => return Err(syn::Error::new_spanned(i, "parts are 1 or 2")),
+                },
+                ("name", Lit::Str(s)) => name = Some(s.value()),
+                ("day" | "part" | "name", _) => {
+                    return Err(syn::Error::new_spanned(lit, "unexpected literal type"))
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &arg.path,
+                        "expected `day`, `part` or `name`",
+                    ))
+                }
+            }
+        }
+
+        let Some(day) = day else {
+            return Err(syn::Error::new(Span::call_site(), "missing `day = N`"));
+        };
+        Ok(Self { day, part, name })
+    }
+}
+
+/// Name of the function standing for the parser of a day, so that the
+/// solutions of that day can find it
+fn parser_ident(day: usize) -> Ident {
+    format_ident!("__aoc_parser_day{:02}", day)
+}
+
+/// Registers a function solving a part. It gets the raw input if it takes a
+/// `&str`, otherwise the output of the parser of the day
+pub fn expand_aoc(args: AocArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let Some(part) = args.part else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "missing `part = 1` or `part = 2`",
+        ));
+    };
+    let part = match part {
+        1 => quote!(::aoc_utils::solution::Part::One),
+        _ => quote!(::aoc_utils::solution::Part::Two),
+    };
+    let name = match &args.name {
+        Some(name) => quote!(Some(#name)),
+        None => quote!(None),
+    };
+
+    let inputs = &function.sig.inputs;
+    let argument = match inputs.first() {
+        Some(FnArg::Typed(argument)) if inputs.len() == 1 => argument,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &function.sig,
+                "solutions take a single argument: the input or the parsed input",
+            ))
+        }
+    };
+    let parser = parser_ident(args.day);
+    // Borrowed parsed inputs are shared by the solutions of the day, the
+    // ones taken by value are parsed again as they are consumed
+    let call_argument = match &*argument.ty {
+        Type::Reference(r) if matches!(&*r.elem, Type::Path(p) if p.path.is_ident("str")) => {
+            quote!(input.text())
+        }
+        Type::Reference(_) => quote!(input.parsed(#parser)),
+        _ => quote!(#parser(input.text())),
+    };
+
+    let day = args.day;
+    let ident = &function.sig.ident;
+    Ok(quote! {
+        #function
+
+        ::aoc_utils::registry::inventory::submit! {
+            ::aoc_utils::registry::Solution {
+                krate: env!("CARGO_CRATE_NAME"),
+                day: #day,
+                part: #part,
+                name: #name,
+                solve: |input: &::aoc_utils::registry::DayInput| {
+                    ::aoc_utils::registry::IntoAnswer::into_answer(#ident(#call_argument))
+                },
+            }
+        }
+    })
+}
+
+/// Makes a function the parser of a day, used by the solutions of that day
+/// that do not take the raw input
+pub fn expand_aoc_parser(args: AocArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    if args.part.is_some() || args.name.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "a parser is shared by all the solutions of the day, it only takes `day = N`",
+        ));
+    }
+
+    let parser = parser_ident(args.day);
+    let ident = &function.sig.ident;
+    Ok(quote! {
+        #function
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        use #ident as #parser;
+    })
+}
@@ -0,0 +1,99 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, LitInt, Token};
+
+use crate::solve_days::{days_or_declared, module_name};
+
+/// Struct for the bench_days! macro, representing the input:
+/// (year, days...)
+/// year is the year of the crate being benchmarked, e.g. `2024` for `aoc2024`
+/// days are integers or ranges of integers, like for solve_days!, and
+/// default to the days with a module
+///
+pub struct YearAndDays {
+    year: LitInt,
+    days: Vec<usize>,
+}
+
+impl Parse for YearAndDays {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let year: LitInt = input.parse().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "expected the year and the days, e.g. `bench_days!(2024, 1..=9, 12)`",
+            )
+        })?;
+        if !(2015..=9999).contains(&year.base10_parse::<u16>()?) {
+            return Err(syn::Error::new_spanned(
+                &year,
+                "expected a year, e.g. `2024`",
+            ));
+        }
+        let mut items: Vec<Expr> = Vec::new();
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            items.extend(Punctuated::<Expr, Token![,]>::parse_terminated(input)?);
+        }
+        let days = days_or_declared(&items)?;
+        if days.is_empty() {
+            return Err(syn::Error::new(Span::call_site(), "no days to benchmark"));
+        }
+        Ok(Self { year, days })
+    }
+}
+
+impl ToTokens for YearAndDays {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let year = &self.year;
+        let krate = format_ident!("aoc{}", year.base10_digits());
+
+        let days = self.days.iter().map(|day| {
+            let module_name = format_ident!("{}", module_name(*day));
+            let name = format!("day {}", day);
+            quote! {
+                match ::aoc_utils::input::load_input(&config, #year, #day) {
+                    Ok(input) => {
+                        c.bench_function(#name, |b| {
+                            b.iter(|| {
+                                ::aoc_utils::memoize::clear_all();
+                                ::#krate::days::#module_name::solve(&input)
+                            })
+                        });
+                        // The parts are benchmarked without the parser, which
+                        // only runs on the first iteration
+                        let day_input = ::aoc_utils::registry::DayInput::new(input.as_str());
+                        for solution in solutions.iter().filter(|s| s.day == #day) {
+                            let name = match solution.name {
+                                Some(name) => format!("{} part {} {}", #name, solution.part, name),
+                                None => format!("{} part {}", #name, solution.part),
+                            };
+                            c.bench_function(&name, |b| {
+                                b.iter(|| {
+                                    ::aoc_utils::memoize::clear_all();
+                                    (solution.solve)(&day_input)
+                                })
+                            });
+                        }
+                    }
+                    Err(e) => eprintln!("Skipping {}: {}", #name, e),
+                }
+            }
+        });
+
+        tokens.extend(quote! {
+            fn criterion_benchmark(c: &mut ::criterion::Criterion) {
+                let config = ::aoc_utils::config::Config::load(
+                    &::aoc_utils::config::ConfigArgs::default(),
+                )
+                .expect("Configuration should be valid");
+                let solutions = ::#krate::solutions();
+                #(#days)*
+            }
+
+            ::criterion::criterion_group!(benches, criterion_benchmark);
+            ::criterion::criterion_main!(benches);
+        });
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+use crate::pattern::{parse_pattern, Segment};
+
+/// Implements `FromStr` following the pattern of the `#[input]` attribute
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Some(attribute) = input.attrs.iter().find(|a| a.path().is_ident("input")) else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "missing the pattern, e.g. `#[input(\"p={x},{y}\")]`",
+        ));
+    };
+    let pattern: LitStr = attribute.parse_args()?;
+    let segments = parse_pattern(&pattern)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "FromInput needs named fields, to use in the pattern",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "FromInput only works on structs",
+            ))
+        }
+    };
+    // Keyed by the name used in the pattern, `{type}` for `r#type`
+    let mut types: HashMap<String, &syn::Type> = fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref().map(|i| (i.unraw().to_string(), &f.ty)))
+        .collect();
+    // Prefixed, so that the generated code cannot clash with the user's names
+    let scanner = format_ident!("__scanner");
+
+    // Each field comes once in the pattern, parsed up to the next literal
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => steps.push(quote!(#scanner.literal(#text)?;)),
+            Segment::Field(field) if field.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    &pattern,
+                    "placeholders name a field, e.g. `{x}`",
+                ))
+            }
+            Segment::Field(field) => {
+                let Some(ty) = types.remove(field) else {
+                    return Err(syn::Error::new_spanned(
+                        &pattern,
+                        match fields
+                            .iter()
+                            .any(|f| f.ident.as_ref().is_some_and(|i| i.unraw() == field))
+                        {
+                            true => format!("`{{{}}}` is used twice", field),
+                            false => format!("`{}` has no field named `{}`", name, field),
+                        },
+                    ));
+                };
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(text)) => text.split('\n').next().unwrap_or_default(),
+                    _ => "",
+                };
+                let local = format_ident!("__field_{}", field);
+                steps.push(quote!(let #local = #scanner.field::<#ty>(#field, #until)?;));
+            }
+        }
+    }
+    if let Some(missing) = fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .find(|i| types.contains_key(&i.unraw().to_string()))
+    {
+        return Err(syn::Error::new_spanned(
+            missing,
+            format!("`{}` is missing from the pattern", missing),
+        ));
+    }
+
+    let field_names = fields.iter().filter_map(|f| f.ident.as_ref());
+    let locals = field_names
+        .clone()
+        .map(|i| format_ident!("__field_{}", i.unraw()));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoc_utils::parse::ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut #scanner = ::aoc_utils::parse::Scanner::new(s);
+                #(#steps)*
+                #scanner.finish()?;
+                Ok(Self { #(#field_names: #locals),* })
+            }
+        }
+    })
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitChar};
+
+/// Implements the conversions between the variants of an enum and the
+/// characters given in their `#[cell]` attribute
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "GridCell only works on enums",
+        ));
+    };
+
+    let mut seen: HashMap<char, &syn::Ident> = HashMap::new();
+    let mut variants = Vec::new();
+    let mut chars = Vec::new();
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "grid cells are a single character, their variants cannot have fields",
+            ));
+        }
+        let Some(attribute) = variant.attrs.iter().find(|a| a.path().is_ident("cell")) else {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("missing the character of `{}`, e.g. `#[cell('#')]`", ident),
+            ));
+        };
+        let c: LitChar = attribute.parse_args()?;
+        if let Some(other) = seen.insert(c.value(), ident) {
+            return Err(syn::Error::new_spanned(
+                &c,
+                format!("{:?} is already the character of `{}`", c.value(), other),
+            ));
+        }
+        variants.push(ident);
+        chars.push(c);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::aoc_utils::grid::InvalidCell;
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                match c {
+                    #(#chars => Ok(Self::#variants),)*
+                    c => Err(::aoc_utils::grid::InvalidCell(c)),
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for char #where_clause {
+            fn from(cell: #name #ty_generics) -> char {
+                match cell {
+                    #(#name::#variants => #chars,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let c = match self {
+                    #(Self::#variants => #chars,)*
+                };
+                write!(f, "{}", c)
+            }
+        }
+
+        impl #impl_generics ::aoc_utils::grid::GridCell for #name #ty_generics #where_clause {}
+    })
+}
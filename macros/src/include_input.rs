@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::LitStr;
+
+/// Embeds the input file if it exists, like `include_str!`. Otherwise warns
+/// and gives a value telling the input is missing, so that only that day
+/// fails, at runtime
+pub fn expand(file: LitStr) -> syn::Result<TokenStream2> {
+    // Relative to the file calling the macro, like include_str!
+    let dir = proc_macro::Span::call_site()
+        .local_file()
+        // Relative to the folder the compiler runs in
+        .and_then(|f| std::env::current_dir().ok().map(|cwd| cwd.join(f)))
+        .and_then(|f| f.parent().map(|p| p.to_path_buf()))
+        .or_else(|| std::env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from))
+        .ok_or_else(|| syn::Error::new_spanned(&file, "cannot locate the calling file"))?;
+    let path = dir.join(file.value());
+    let path = path
+        .to_str()
+        .ok_or_else(|| syn::Error::new_spanned(&file, "the path is not valid UTF-8"))?;
+
+    if std::path::Path::new(path).is_file() {
+        return Ok(quote! {
+            ::aoc_utils::input::EmbeddedInput::Present(include_str!(#path))
+        });
+    }
+
+    // Stable proc macros cannot warn directly, using a deprecated item does
+    let note = format!(
+        "input missing: {}, the day will fail at runtime. Rebuild after adding it",
+        path
+    );
+    Ok(quote! {
+        {
+            #[deprecated(note = #note)]
+            const INPUT_MISSING: () = ();
+            let _ = INPUT_MISSING;
+            ::aoc_utils::input::EmbeddedInput::Missing(#path)
+        }
+    })
+}
@@ -1,121 +1,277 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
 
-use quote::{format_ident, quote, ToTokens};
-use syn::{parse::Parse, parse::ParseStream, parse_macro_input, Token};
+use quote::quote;
+use syn::parse_macro_input;
 
-/// Macro that transforms a range of numbers into importing a day module and running it
-/// to solve the problem
+mod aoc;
+mod bench_days;
+mod from_input;
+mod grid_cell;
+mod include_input;
+mod memoize;
+mod pattern;
+mod run_day;
+mod scan;
+mod solve_days;
+
+/// Macro that transforms a list of days into importing the day modules and
+/// running them to solve the problem
 ///
 /// For example:
 ///
 /// ```rust
 /// use aoc_macros::solve_days;
-/// solve_days!(1, 2, args)
+/// solve_days!(1..=2, 12, args.days, runner)
 /// ```
 ///
 /// Will be expanded to:
 ///
 /// ```rust
 /// // Day 1
-/// if args.all || args.days.contains(&1) {
-///     println!("Solving Day {}.", 1);
-///     let results = days::day01::solve();
-///     if let Some(r) = results.0 { println!("Part 1 result: {:?}", r) };
-///     if let Some(r) = results.1 { println!("Part 2 result: {:?}", r) };
+/// if args.days.selects(1) {
+///     runner.run_day(1, days::day01::solve);
 /// }
 /// // Day 2
-/// if args.all || args.days.contains(&2) {
-///     println!("Solving Day {}.", 2);
-///     let results = days::day02::solve();
-///     if let Some(r) = results.0 { println!("Part 1 result: {:?}", r) };
-///     if let Some(r) = results.1 { println!("Part 2 result: {:?}", r) };
+/// if args.days.selects(2) {
+///     runner.run_day(2, days::day02::solve);
+/// }
+/// // Day 12
+/// if args.days.selects(12) {
+///     runner.run_day(12, days::day12::solve);
 /// }
 /// ```
+///
+/// Days are numbers or ranges (`1..=9`, `1..10`) between 1 and 25. Without
+/// them, `solve_days!(args.days, runner)` solves every day with a module in
+/// `src/days/mod.rs`, which is how the year crates list their days once.
+/// `args.days` is an `aoc_utils::runner::DaysArgs`, telling which days were
+/// asked for on the command line, and `runner` is an `aoc_utils::Runner`,
+/// taking care of loading the input and printing the results.
+/// Each day must have a module declared in `src/days/mod.rs`
 #[proc_macro]
 pub fn solve_days(attr: TokenStream) -> TokenStream {
-    //println!("attr: {:?}", attr);
-    let a: DaysRangeAndArgs = parse_macro_input!(attr as DaysRangeAndArgs);
-    quote!(#a).into()
+    let days = parse_macro_input!(attr as solve_days::DaysAndArgs);
+    quote!(#days).into()
+}
 
-    // let start: usize = 1;
-    // let stop = 2;
-    // let args = format_ident!("{}", "args");
-    //println!("item: {:?}", item);
-    //     let mut expanded = quote! {};
-    //
-    //     // Now just loop from day start to stop
-    //     for i in start..=stop {
-    //         let module_name = if i < 10 {
-    //             format_ident!("day0{}", i)
-    //         } else {
-    //             format_ident!("day{}", i)
-    //         };
-    //         expanded.extend(quote! {
-    //             // Day #i
-    //             if #args.all || #args.days.contains(&#i) {
-    //             println!("Solving Day {}.", #i);
-    //             let results = days::#module_name::solve();
-    //             println!("Part 1 result: {:?}", results.0);
-    //             println!("Part 2 result: {:?}", results.1);
-    //             }
-    //
-    //         });
-    //     }
-    //
-    //     TokenStream::from(expanded)
+/// Macro generating the criterion benchmarks of the days of a year, to use
+/// as the whole content of `benches/benchmarks.rs`:
+///
+/// ```rust
+/// aoc_macros::bench_days!(2024);
+/// ```
+///
+/// Each day gets a `day N` benchmark of its `solve` function, on the input
+/// the runner would use. Days written with `#[aoc]` also get a benchmark per
+/// registered part, e.g. `day 1 part 2` and `day 1 part 2 counts` for a named
+/// implementation. Days are given like for `solve_days!`, after the year, and
+/// default to the days with a module, like for `solve_days!`. Days without
+/// an input are skipped.
+#[proc_macro]
+pub fn bench_days(attr: TokenStream) -> TokenStream {
+    let days = parse_macro_input!(attr as bench_days::YearAndDays);
+    quote!(#days).into()
 }
 
-/// Struct for the solve_days! macro, representing the input:
-/// (start, stop, args)
-/// Start in an integer, in the range 1..=25
-/// Stop in an integer, in the range 2..=25, larger or equal to start
-/// args is an identifier, representing the arguments to the program
-///
-struct DaysRangeAndArgs {
-    start: usize,
-    stop: usize,
-    args: syn::Ident,
+/// Macro dispatching a day number to the `solve` function of its module,
+/// through `aoc_utils::solution::run`:
+///
+/// ```rust
+/// pub fn run(day: usize, part: Part, input: &str) -> Result<Answer, SolveError> {
+///     aoc_macros::run_day!(day, part, input)
+/// }
+/// ```
+///
+/// Days are given like for `solve_days!`, before the day, and default to the
+/// days with a module. Other days give `SolveError::UnknownDay`.
+#[proc_macro]
+pub fn run_day(attr: TokenStream) -> TokenStream {
+    let run = parse_macro_input!(attr as run_day::RunDay);
+    quote!(#run).into()
 }
 
-impl Parse for DaysRangeAndArgs {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let start_lit: syn::LitInt = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let stop_lit: syn::LitInt = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let args = input.parse::<syn::Ident>()?;
+/// Registers a function solving one part of a day in the registry of the
+/// crate, see `aoc_utils::registry`.
+///
+/// For example:
+///
+/// ```rust
+/// #[aoc(day = 5, part = 1)]
+/// fn part_1(updates: &Updates) -> usize { ... }
+///
+/// #[aoc(day = 5, part = 2, name = "fast")]
+/// fn part_2_fast(input: &str) -> usize { ... }
+/// ```
+///
+/// Functions taking a `&str` get the raw input, the others get the output of
+/// the `#[aoc_parser]` of the day, by reference or by value. The input is
+/// parsed once for all the functions taking it by reference, the ones taking
+/// it by value parse their own copy. They return anything convertible to an
+/// answer, or an `Option` of it.
+/// Named functions are alternatives to the main implementation of the part.
+#[proc_macro_attribute]
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as aoc::AocArgs);
+    let function = parse_macro_input!(item as syn::ItemFn);
+    aoc::expand_aoc(args, function)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-        let start = start_lit.base10_parse::<usize>()?;
-        let stop = stop_lit.base10_parse::<usize>()?;
-        Ok(Self { start, stop, args })
-    }
+/// Marks the function parsing the input of a day, for the `#[aoc]` functions
+/// of the same module that do not take the raw input:
+///
+/// ```rust
+/// #[aoc_parser(day = 5)]
+/// fn parse(input: &str) -> Updates { ... }
+/// ```
+///
+/// The parsed input is kept for all the solutions of the input, so it cannot
+/// borrow from the input: parsers return owned data.
+#[proc_macro_attribute]
+pub fn aoc_parser(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as aoc::AocArgs);
+    let function = parse_macro_input!(item as syn::ItemFn);
+    aoc::expand_aoc_parser(args, function)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
-impl ToTokens for DaysRangeAndArgs {
-    fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let start = self.start;
-        let stop = self.stop;
-        let args = &self.args;
+/// Implements `FromStr` for a struct, following the pattern given in the
+/// `#[input]` attribute, where `{field}` is the value of a field:
+///
+/// ```rust
+/// #[derive(FromInput)]
+/// #[input("p={x},{y} v={dx},{dy}")]
+/// struct Robot {
+///     x: usize,
+///     y: usize,
+///     dx: isize,
+///     dy: isize,
+/// }
+///
+/// let robot: Robot = "p=0,4 v=3,-3".parse()?;
+/// let robots: Vec<Robot> = aoc_utils::parse::lines(input)?;
+/// ```
+///
+/// Each field is parsed with its own `FromStr`, from the text up to the next
+/// literal part of the pattern, ignoring the spaces around it. Patterns can
+/// span several lines, to parse blocks with `aoc_utils::parse::blocks`.
+/// `{{` and `}}` stand for braces.
+///
+/// Errors are `aoc_utils::parse::ParseError`, telling what did not match and
+/// on which line.
+#[proc_macro_derive(FromInput, attributes(input))]
+pub fn from_input(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    from_input::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Scans a line with a pattern, returning its values as a tuple in a
+/// `Result`, e.g.:
+///
+/// ```rust
+/// let (register, value) = scan!("Register {}: {}", line => char, u64)?;
+/// let program = scan!("Program: {}", line => String)?;
+/// ```
+///
+/// Each `{}` is parsed with the `FromStr` of its type, from the text up to
+/// the next literal part of the pattern, ignoring the spaces around it.
+/// With a single placeholder, the value is returned as is. The number of
+/// placeholders and types must match. `{{` and `}}` stand for braces.
+///
+/// Errors are `aoc_utils::parse::ParseError`, like with `#[derive(FromInput)]`
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    let scan = parse_macro_input!(input as scan::Scan);
+    scan::expand(scan)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Makes an enum the cells of a grid, each variant being written as the
+/// character of its `#[cell]` attribute in the input:
+///
+/// ```rust
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+/// enum Tile {
+///     #[cell('#')]
+///     Wall,
+///     #[cell('.')]
+///     Floor,
+/// }
+///
+/// let grid: Grid<Tile> = input.parse()?;
+/// println!("{}", grid);
+/// ```
+///
+/// Implements `TryFrom<char>`, `From<Tile> for char`, `Display` and
+/// `aoc_utils::grid::GridCell`. The enum must also be `Copy`.
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn grid_cell(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    grid_cell::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-        // Now just loop from day start to stop
-        for i in start..=stop {
-            let module_name = if i < 10 {
-                format_ident!("day0{}", i)
-            } else {
-                format_ident!("day{}", i)
-            };
-            tokens.extend(quote! {
-                // Day #i
-                if #args.all || #args.days.contains(&#i) {
-                println!("Solving Day {}.", #i);
-                let results = days::#module_name::solve();
-                if let Some(r) = results.0 { println!("Part 1 result: {:?}", r) };
-                if let Some(r) = results.1 { println!("Part 2 result: {:?}", r) };
-                }
+/// Embeds an input file in the binary, relative to the calling file like
+/// `include_str!`, as an `aoc_utils::input::EmbeddedInput`:
+///
+/// ```rust
+/// const INPUT: EmbeddedInput = include_input!("input/day05.txt");
+///
+/// runner.run_embedded(5, INPUT, days::day05::solve);
+/// ```
+///
+/// A missing file does not stop the build: it gives a warning, and the day
+/// reports the missing input when it runs. The file is not watched then, so
+/// rebuild after adding it. The warning comes from a deprecated item, so it is
+/// hidden where warnings are allowed, like in the year folders: the runner
+/// warns again when it meets the missing input.
+#[proc_macro]
+pub fn include_input(input: TokenStream) -> TokenStream {
+    let file = parse_macro_input!(input as syn::LitStr);
+    include_input::expand(file)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-            });
-        }
-    }
+/// Caches the results of a function, keyed by its arguments, which must be
+/// hashable and owned, or references to something that can be owned:
+///
+/// ```rust
+/// #[memoize]
+/// fn count(stone: u64, blinks: usize) -> usize { ... }
+///
+/// #[memoize(cache = "array", size = 128)]
+/// fn fibonacci(n: usize) -> u64 { ... }
+///
+/// #[memoize(cache = "lru", size = 10000)]
+/// fn arrangements(design: String, towels: usize) -> usize { ... }
+/// ```
+///
+/// The cache is a `HashMap` by default. An array cache holds the values of a
+/// single integer argument below `size`, an LRU cache the `size` most
+/// recently used values. Ignored arguments are left out of the key: only
+/// ignore arguments that never change the result, e.g. a logger. The cache
+/// is shared by the whole process, so a function depending on data that
+/// changes from one input to the next should not use `#[memoize]`: it keeps
+/// a local cache instead, created for each input and passed down the
+/// recursion, like the towel patterns of 2024 day 19.
+///
+/// Also generates `count_clear()` and `count_stats()`, giving the hits and
+/// misses of the cache in debug builds. The runner clears all the caches
+/// before solving, with `aoc_utils::memoize::clear_all()`.
+/// Only works on free functions, not on methods or generic functions.
+#[proc_macro_attribute]
+pub fn memoize(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as memoize::MemoizeArgs);
+    let function = parse_macro_input!(item as syn::ItemFn);
+    memoize::expand(args, function)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
@@ -0,0 +1,228 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, FnArg, ItemFn, Lit, MetaNameValue, Pat, ReturnType, Token, Type};
+
+/// Kind of cache storing the values of a memoized function
+enum CacheKind {
+    Hash,
+    Array(usize),
+    Lru(usize),
+}
+
+/// Arguments of the `#[memoize]` attribute, all optional:
+/// `cache = "lru", size = 1000, ignore = "patterns"`
+/// cache is "hash" (the default), "array" or "lru"
+/// size is the length of an array cache, or the capacity of an LRU cache
+/// ignore lists the arguments that are not part of the key, separated by
+/// commas
+///
+pub struct MemoizeArgs {
+    cache: CacheKind,
+    ignore: Vec<String>,
+}
+
+impl Parse for MemoizeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut cache = None;
+        let mut size = None;
+        let mut ignore = Vec::new();
+
+        for arg in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let key = arg
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            let Expr::Lit(ExprLit { lit, .. }) = &arg.value else {
+                return Err(syn::Error::new_spanned(&arg.value, "expected a literal"));
+            };
+            match (key.as_str(), lit) {
+                ("cache", Lit::Str(s)) => match s.value().as_str() {
+                    kind @ ("hash" | "array" | "lru") => {
+                        cache = Some((kind.to_string(), s.clone()))
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            s,
+                            "caches are \"hash\", \"array\" or \"lru\"",
+                        ))
+                    }
+                },
+                ("size", Lit::Int(i)) => match i.base10_parse::<usize>()? {
+                    0 => return Err(syn::Error::new_spanned(i, "the size cannot be 0")),
+                    s => size = Some(s),
+                },
+                ("ignore", Lit::Str(s)) => ignore.extend(
+                    s.value()
+                        .split(',')
+                        .map(|a| a.trim().to_string())
+                        .filter(|a| !a.is_empty()),
+                ),
+                ("cache" | "size" | "ignore", _) => {
+                    return Err(syn::Error::new_spanned(lit, "unexpected literal type"))
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &arg.path,
+                        "expected `cache`, `size` or `ignore`",
+                    ))
+                }
+            }
+        }
+
+        let cache = match (cache, size) {
+            (None, None) => CacheKind::Hash,
+            (Some((kind, _)), None) if kind == "hash" => CacheKind::Hash,
+            (Some((kind, _)), Some(size)) if kind == "array" => CacheKind::Array(size),
+            (Some((kind, _)), Some(size)) if kind == "lru" => CacheKind::Lru(size),
+            (Some((_, s)), None) => {
+                return Err(syn::Error::new_spanned(
+                    s,
+                    "missing the size of the cache, e.g. `size = 1000`",
+                ))
+            }
+            (_, Some(_)) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "only \"array\" and \"lru\" caches have a size",
+                ))
+            }
+        };
+        Ok(Self { cache, ignore })
+    }
+}
+
+/// Wraps the function with a lookup in a static cache, keyed by its
+/// arguments. Also adds the functions clearing the cache and giving its
+/// statistics, registered so that `aoc_utils::memoize::clear_all` finds them
+pub fn expand(args: MemoizeArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let sig = &function.sig;
+    let name = &sig.ident;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "memoized functions cannot be generic, their cache is a static",
+        ));
+    }
+    let ReturnType::Type(_, output) = &sig.output else {
+        return Err(syn::Error::new_spanned(
+            sig,
+            "nothing to memoize, the function returns nothing",
+        ));
+    };
+
+    // The key is made of owned copies of the arguments that are not ignored
+    let mut key_types = Vec::new();
+    let mut key_values = Vec::new();
+    let mut arguments = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(argument) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "memoized functions cannot take `self`, the cache is shared by all calls",
+            ));
+        };
+        let Pat::Ident(pattern) = argument.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &argument.pat,
+                "arguments of memoized functions must be plain names",
+            ));
+        };
+        let ident = &pattern.ident;
+        arguments.push(ident.to_string());
+        if args.ignore.contains(&ident.to_string()) {
+            continue;
+        }
+        match argument.ty.as_ref() {
+            Type::Reference(reference) => {
+                let ty = &reference.elem;
+                key_types.push(quote!(<#ty as ::std::borrow::ToOwned>::Owned));
+                key_values.push(quote!(::std::borrow::ToOwned::to_owned(#ident)));
+            }
+            ty => {
+                key_types.push(quote!(#ty));
+                key_values.push(quote!(::std::clone::Clone::clone(&#ident)));
+            }
+        }
+    }
+    if let Some(unknown) = args.ignore.iter().find(|i| !arguments.contains(i)) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{}` has no argument named `{}`", name, unknown),
+        ));
+    }
+
+    let key_count = key_types.len();
+    let (key_type, key_value) = match key_count {
+        1 => (key_types.remove(0), key_values.remove(0)),
+        _ => (quote!((#(#key_types),*)), quote!((#(#key_values),*))),
+    };
+    let cache = match args.cache {
+        CacheKind::Hash => quote!(::aoc_utils::memoize::HashCache<#key_type, #output>),
+        CacheKind::Array(size) => {
+            if key_count != 1 {
+                return Err(syn::Error::new_spanned(
+                    &sig.inputs,
+                    "array caches are keyed by a single integer argument",
+                ));
+            }
+            quote!(::aoc_utils::memoize::ArrayCache<#output, #size>)
+        }
+        CacheKind::Lru(size) => {
+            quote!(::aoc_utils::memoize::LruCache<#key_type, #output, #size>)
+        }
+    };
+    // Integers out of the array are not cached
+    let key_value = match args.cache {
+        CacheKind::Array(_) => quote!(usize::try_from(#key_value).unwrap_or(usize::MAX)),
+        _ => key_value,
+    };
+
+    let memo = format_ident!("__MEMOIZE_{}", name.to_string().to_uppercase());
+    let clear = format_ident!("{}_clear", name);
+    let stats = format_ident!("{}_stats", name);
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let block = &function.block;
+    let clear_doc = format!("Clears the cache of [`{}`]", name);
+    let stats_doc = format!(
+        "Hits and misses of the cache of [`{}`], counted in debug builds",
+        name
+    );
+    Ok(quote! {
+        #[doc(hidden)]
+        static #memo: ::aoc_utils::memoize::Memo<#cache> =
+            ::aoc_utils::memoize::Memo::new(<#cache>::new());
+
+        #(#attrs)*
+        #vis #sig {
+            let __memoize_key = #key_value;
+            if let Some(value) = #memo.get(&__memoize_key) {
+                return value;
+            }
+            let __memoize_value = (move || -> #output #block)();
+            #memo.insert(__memoize_key, ::std::clone::Clone::clone(&__memoize_value));
+            __memoize_value
+        }
+
+        #[doc = #clear_doc]
+        #vis fn #clear() {
+            #memo.clear();
+        }
+
+        #[doc = #stats_doc]
+        #vis fn #stats() -> ::aoc_utils::memoize::CacheStats {
+            #memo.stats()
+        }
+
+        ::aoc_utils::registry::inventory::submit! {
+            ::aoc_utils::memoize::Memoized {
+                name: concat!(module_path!(), "::", stringify!(#name)),
+                clear: #clear,
+                stats: #stats,
+            }
+        }
+    })
+}
@@ -0,0 +1,95 @@
+use syn::LitStr;
+
+/// A piece of the pattern of `#[input("...")]` or `scan!`
+#[derive(Debug, PartialEq)]
+pub enum Segment {
+    /// Text that must be there as is
+    Literal(String),
+    /// `{name}` or `{}`, a value to parse
+    Field(String),
+}
+
+/// Splits a pattern like `p={x},{y}` into its literal text and fields.
+/// `{{` and `}}` stand for braces
+pub fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| syn::Error::new_spanned(pattern, message);
+    let text = pattern.value();
+
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(error("unclosed `{`, use `{{` for a brace")),
+                    }
+                }
+                let name = name.trim();
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                if let Some(Segment::Field(_)) = segments.last() {
+                    return Err(error("placeholders need some text between them"));
+                }
+                segments.push(Segment::Field(name.to_string()));
+            }
+            '}' => return Err(error("unmatched `}`, use `}}` for a brace")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pattern() {
+        let pattern: LitStr = syn::parse_quote!("p={x},{y} {{v}}");
+        assert_eq!(
+            parse_pattern(&pattern).unwrap(),
+            vec![
+                Segment::Literal("p=".to_string()),
+                Segment::Field("x".to_string()),
+                Segment::Literal(",".to_string()),
+                Segment::Field("y".to_string()),
+                Segment::Literal(" {v}".to_string()),
+            ]
+        );
+
+        let positional: LitStr = syn::parse_quote!("Register {}: {}");
+        assert_eq!(
+            parse_pattern(&positional).unwrap(),
+            vec![
+                Segment::Literal("Register ".to_string()),
+                Segment::Field(String::new()),
+                Segment::Literal(": ".to_string()),
+                Segment::Field(String::new()),
+            ]
+        );
+
+        let adjacent: LitStr = syn::parse_quote!("{x}{y}");
+        assert!(parse_pattern(&adjacent).is_err());
+        let unmatched: LitStr = syn::parse_quote!("x}");
+        assert!(parse_pattern(&unmatched).is_err());
+        let unclosed: LitStr = syn::parse_quote!("p={x");
+        assert!(parse_pattern(&unclosed).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Token};
+
+use crate::solve_days::{days_or_declared, module_name};
+
+/// Struct for the run_day! macro, representing the input:
+/// (days..., day, part, input)
+/// days are integers or ranges of integers, like for solve_days!, and
+/// default to the days with a module
+/// day, part and input are the expressions to pass to
+/// `aoc_utils::solution::run`
+///
+pub struct RunDay {
+    days: Vec<usize>,
+    day: Expr,
+    part: Expr,
+    input: Expr,
+}
+
+impl Parse for RunDay {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items: Vec<Expr> = Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+        if items.len() < 3 {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected the day, part and input, e.g. `run_day!(day, part, input)`",
+            ));
+        }
+        let input = items.pop().expect("checked above");
+        let part = items.pop().expect("checked above");
+        let day = items.pop().expect("checked above");
+
+        let days = days_or_declared(&items)?;
+        Ok(Self {
+            days,
+            day,
+            part,
+            input,
+        })
+    }
+}
+
+impl ToTokens for RunDay {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let (day, part, input) = (&self.day, &self.part, &self.input);
+        // Each arm uses the literal day, so that the expression of the day is
+        // only evaluated once
+        let arms = self.days.iter().map(|number| {
+            let module_name = format_ident!("{}", module_name(*number));
+            quote! {
+                #number => ::aoc_utils::solution::run(#number, days::#module_name::solve, #part, #input),
+            }
+        });
+
+        tokens.extend(quote! {
+            match #day {
+                #(#arms)*
+                other => Err(::aoc_utils::solution::SolveError::UnknownDay(other)),
+            }
+        });
+    }
+}
@@ -0,0 +1,106 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, LitStr, Token, Type};
+
+use crate::pattern::{parse_pattern, Segment};
+
+/// Input of the scan! macro: (pattern, text => types...)
+/// pattern is a string literal with a `{}` per value
+/// text is an expression giving the text to scan
+/// types are the types of the values, in the order of the placeholders
+///
+pub struct Scan {
+    pattern: LitStr,
+    text: Expr,
+    types: Vec<Type>,
+}
+
+impl Parse for Scan {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pattern = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let text = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let types = Punctuated::<Type, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+        Ok(Self {
+            pattern,
+            text,
+            types,
+        })
+    }
+}
+
+/// Parses the text with a function following the pattern step by step
+pub fn expand(scan: Scan) -> syn::Result<TokenStream2> {
+    let pattern = &scan.pattern;
+    let segments = parse_pattern(pattern)?;
+
+    let placeholders = segments
+        .iter()
+        .filter(|s| matches!(s, Segment::Field(_)))
+        .count();
+    if placeholders != scan.types.len() {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            format!(
+                "{} placeholders in the pattern, but {} types given",
+                placeholders,
+                scan.types.len()
+            ),
+        ));
+    }
+    if placeholders == 0 {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            "nothing to scan, add a `{}`",
+        ));
+    }
+
+    let mut types = scan.types.iter();
+    let mut values = Vec::new();
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => steps.push(quote!(scanner.literal(#text)?;)),
+            Segment::Field(name) if !name.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    pattern,
+                    format!("placeholders of scan! are `{{}}`, not `{{{}}}`", name),
+                ))
+            }
+            Segment::Field(_) => {
+                let ty = types.next().expect("as many types as placeholders");
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(text)) => text.split('\n').next().unwrap_or_default(),
+                    _ => "",
+                };
+                let name = format!("value {}", values.len() + 1);
+                let value = quote::format_ident!("value_{}", values.len());
+                steps.push(quote!(let #value = scanner.field::<#ty>(#name, #until)?;));
+                values.push(value);
+            }
+        }
+    }
+
+    let types = &scan.types;
+    let (output, result) = match values.len() {
+        1 => (quote!(#(#types)*), quote!(#(#values)*)),
+        _ => (quote!((#(#types),*)), quote!((#(#values),*))),
+    };
+    let text = &scan.text;
+    Ok(quote! {
+        {
+            fn scan(text: &str) -> Result<#output, ::aoc_utils::parse::ParseError> {
+                let mut scanner = ::aoc_utils::parse::Scanner::new(text);
+                #(#steps)*
+                scanner.finish()?;
+                Ok(#result)
+            }
+            scan(&#text)
+        }
+    })
+}
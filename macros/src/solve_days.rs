@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, ExprRange, Item, Lit, RangeLimits, Token};
+
+/// Where the day modules of a year crate are declared, relative to its manifest
+const DAYS_MODULE_FILE: &str = "src/days/mod.rs";
+
+/// Struct for the solve_days! macro, representing the input:
+/// (days..., args, runner)
+/// days are integers or ranges of integers (`1..=9` or `1..10`), in 1..=25.
+/// Without days, the days are the ones with a module in `src/days/mod.rs`
+/// args is an expression with a `selects(day)` method, usually an
+/// `aoc_utils::runner::DaysArgs`
+/// runner is an expression, the `aoc_utils::Runner` to use
+///
+pub struct DaysAndArgs {
+    days: Vec<usize>,
+    args: Expr,
+    runner: Expr,
+}
+
+impl Parse for DaysAndArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items: Vec<Expr> = Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect();
+        if items.len() < 2 {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected days, args and runner, e.g. `solve_days!(1..=9, 12, args, runner)`",
+            ));
+        }
+        let runner = items.pop().expect("checked above");
+        let args = items.pop().expect("checked above");
+
+        let days = days_or_declared(&items)?;
+        Ok(Self { days, args, runner })
+    }
+}
+
+/// Days of a list of days and ranges of days, each checked to have a
+/// module. An empty list gives the days with a module
+pub fn days_or_declared(items: &[Expr]) -> syn::Result<Vec<usize>> {
+    if items.is_empty() {
+        return declared_days();
+    }
+    let days = parse_day_list(items)?;
+    check_modules(&days)?;
+    Ok(days.into_iter().map(|(day, _)| day).collect())
+}
+
+/// Days of a list of days and ranges of days, sorted, each with the item of
+/// the list it comes from to point at it in errors
+pub fn parse_day_list(items: &[Expr]) -> syn::Result<Vec<(usize, Expr)>> {
+    let mut days = Vec::new();
+    let mut seen = HashSet::new();
+    for item in items {
+        for day in parse_days(item)? {
+            if !seen.insert(day) {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    format!("day {} is listed twice", day),
+                ));
+            }
+            days.push((day, item.clone()));
+        }
+    }
+    days.sort_by_key(|(day, _)| *day);
+    Ok(days)
+}
+
+/// Days of one item of the list: a single day or a range of days
+fn parse_days(item: &Expr) -> syn::Result<Vec<usize>> {
+    let days = match item {
+        Expr::Lit(_) => vec![parse_day(item)?],
+        Expr::Range(ExprRange {
+            start: Some(start),
+            limits,
+            end: Some(end),
+            ..
+        }) => {
+            let start = parse_day(start)?;
+            let end = parse_day(end)?;
+            let days: Vec<usize> = match limits {
+                RangeLimits::Closed(_) => (start..=end).collect(),
+                RangeLimits::HalfOpen(_) => (start..end).collect(),
+            };
+            if days.is_empty() {
+                return Err(syn::Error::new_spanned(item, "empty range of days"));
+            }
+            days
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "expected a day or a range of days, e.g. `12` or `1..=9`",
+            ))
+        }
+    };
+    Ok(days)
+}
+
+fn parse_day(expr: &Expr) -> syn::Result<usize> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Int(lit), ..
+    }) = expr
+    else {
+        return Err(syn::Error::new_spanned(expr, "expected a day number"));
+    };
+    match lit.base10_parse::<usize>()? {
+        day @ 1..=25 => Ok(day),
+        _ => Err(syn::Error::new_spanned(lit, "days go from 1 to 25")),
+    }
+}
+
+/// Name of the module of a day, e.g. `day05`
+pub fn module_name(day: usize) -> String {
+    format!("day{:02}", day)
+}
+
+/// Modules declared in `src/days/mod.rs` of the crate being compiled, if it
+/// can be read. Otherwise the compiler will complain about the paths.
+fn declared_modules() -> Option<HashSet<String>> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    let content =
+        std::fs::read_to_string(std::path::Path::new(&dir).join(DAYS_MODULE_FILE)).ok()?;
+    let file = syn::parse_file(&content).ok()?;
+    let modules = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(module) => Some(module.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+    Some(modules)
+}
+
+/// Days with a module declared in `src/days/mod.rs`, sorted
+fn declared_days() -> syn::Result<Vec<usize>> {
+    let modules = declared_modules().ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "cannot read the day modules from {}, list the days instead",
+                DAYS_MODULE_FILE
+            ),
+        )
+    })?;
+    Ok((1..=25)
+        .filter(|&day| modules.contains(&module_name(day)))
+        .collect())
+}
+
+/// Checks that every day has a module, pointing at the day in the macro
+/// call if not
+fn check_modules(days: &[(usize, Expr)]) -> syn::Result<()> {
+    let Some(modules) = declared_modules() else {
+        return Ok(());
+    };
+    for (day, item) in days {
+        let module = module_name(*day);
+        if !modules.contains(&module) {
+            return Err(syn::Error::new_spanned(
+                item,
+                format!(
+                    "no module for day {}: `pub mod {};` is missing from {}",
+                    day, module, DAYS_MODULE_FILE
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl ToTokens for DaysAndArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let args = &self.args;
+        let runner = &self.runner;
+
+        for day in &self.days {
+            let module_name = format_ident!("{}", module_name(*day));
+            tokens.extend(quote! {
+                if #args.selects(#day) {
+                    #runner.run_day(#day, days::#module_name::solve);
+                }
+            });
+        }
+    }
+}
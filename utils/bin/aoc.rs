@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_utils::config::{Config, ConfigArgs};
+use aoc_utils::encryption::Key;
+use aoc_utils::input::{self, InputError};
+use clap::{Parser, Subcommand};
+
+/// Manages the puzzle inputs of the workspace
+#[derive(Parser, Debug)]
+struct Args {
+    /// Year to work on, defaults to the `year` configuration entry
+    #[arg(long, short, global = true)]
+    year: Option<u16>,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Downloads the inputs of the given days into the input folder
+    Download {
+        #[arg(long, short, required = true)]
+        days: Vec<usize>,
+    },
+    /// Encrypts the inputs of the year, so they can be committed
+    Encrypt,
+    /// Decrypts the encrypted inputs of the year
+    Decrypt,
+    /// Prints a new random key, to put in the `key` configuration entry
+    Keygen,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let config = match Config::load(&args.config) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    aoc_utils::logging::init(&config);
+
+    if let Command::Keygen = args.command {
+        println!("{}", Key::generate());
+        return ExitCode::SUCCESS;
+    }
+    let Some(year) = args.year.or(config.year) else {
+        eprintln!("No year given, use --year or set `year` in aoc.toml");
+        return ExitCode::FAILURE;
+    };
+
+    match args.command {
+        Command::Keygen => unreachable!("Handled above"),
+        Command::Encrypt => report_written(input::encrypt_inputs(&config, year)),
+        Command::Decrypt => report_written(input::decrypt_inputs(&config, year)),
+        Command::Download { days } => {
+            let mut exit_code = ExitCode::SUCCESS;
+            for day in days {
+                match input::download_input(&config, year, day) {
+                    Ok(path) => println!("Day {}: saved to {}", day, path.display()),
+                    Err(e) => {
+                        eprintln!("Day {}: {}", day, e);
+                        exit_code = ExitCode::FAILURE;
+                    }
+                }
+            }
+            exit_code
+        }
+    }
+}
+
+/// Prints the files written by a command, or the error that stopped it
+fn report_written(written: Result<Vec<PathBuf>, InputError>) -> ExitCode {
+    match written {
+        Ok(paths) => {
+            paths.iter().for_each(|p| println!("Wrote {}", p.display()));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,18 +1,22 @@
-use crate::input;
+use aoc_macros::{aoc, aoc_parser};
+use aoc_utils::registry;
+use aoc_utils::solution::Answer;
 
-pub fn solve() -> (Option<usize>, Option<usize>) {
-    let input = input::DAY_ % day % _INPUT;
-
-    let part_1_total = solve_part_1(input);
-    let part_2_total = solve_part_2(input);
+pub fn solve(input: &str) -> (Option<Answer>, Option<Answer>) {
+    registry::solve(env!("CARGO_CRATE_NAME"), DAY, input)
+}
 
-    (part_1_total, part_2_total)
+#[aoc_parser(day = DAY)]
+fn parse(input: &str) -> Vec<String> {
+    input.lines().map(str::to_string).collect()
 }
 
-fn solve_part_1(input: &str) -> Option<usize> {
+#[aoc(day = DAY, part = 1)]
+fn solve_part_1(lines: &[String]) -> Option<usize> {
     None
 }
 
-fn solve_part_2(input: &str) -> Option<usize> {
+#[aoc(day = DAY, part = 2)]
+fn solve_part_2(lines: &[String]) -> Option<usize> {
     None
 }
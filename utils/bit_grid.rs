@@ -0,0 +1,299 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Index};
+
+use super::grid::{Coord, Grid};
+
+/// A grid of booleans packed in `u64` words, e.g. to keep track of visited
+/// cells. Each row starts on a new word, so that the rows can be shifted
+/// word by word. The bits past the width of a row are always 0
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    row_words: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create a new grid with the indicated dimensions, with no cell set
+    pub fn new(width: usize, height: usize) -> Self {
+        let row_words = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            row_words,
+            words: vec![0; row_words * height],
+        }
+    }
+
+    /// Word and bit of a cell, if it is within the grid
+    #[inline]
+    fn bit_of(&self, coord: impl Coord) -> Option<(usize, u64)> {
+        let (x, y) = coord.xy()?;
+        (x < self.width && y < self.height).then_some((y * self.row_words + x / 64, 1 << (x % 64)))
+    }
+
+    /// Checks if a cell is set, cells out of the grid never are
+    #[inline]
+    pub fn get(&self, coord: impl Coord) -> bool {
+        self.bit_of(coord)
+            .is_some_and(|(word, bit)| self.words[word] & bit != 0)
+    }
+
+    /// Sets or unsets a cell. Panics out of the grid
+    #[track_caller]
+    pub fn set(&mut self, coord: impl Coord, value: bool) {
+        let (word, bit) = self.bit_or_panic(coord);
+        match value {
+            true => self.words[word] |= bit,
+            false => self.words[word] &= !bit,
+        }
+    }
+
+    /// Sets a cell, returning true if it was not set yet, like
+    /// `HashSet::insert`. Panics out of the grid
+    #[track_caller]
+    pub fn insert(&mut self, coord: impl Coord) -> bool {
+        let (word, bit) = self.bit_or_panic(coord);
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    #[track_caller]
+    fn bit_or_panic(&self, coord: impl Coord) -> (usize, u64) {
+        match self.bit_of(coord) {
+            Some(bit) => bit,
+            None => panic!(
+                "{:?} is out of the grid, which is {} wide and {} high",
+                coord, self.width, self.height
+            ),
+        }
+    }
+
+    /// Unsets all the cells
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of cells that are set
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Positions of the cells that are set, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, x) = (i / self.row_words, i % self.row_words * 64);
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits.wrapping_sub(1);
+                (bit < 64).then_some((x + bit, y))
+            })
+        })
+    }
+
+    /// Sets the cells that are set in the other grid, which must have the
+    /// same size
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a |= b);
+    }
+
+    /// Unsets the cells that are not set in the other grid, which must have
+    /// the same size
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= b);
+    }
+
+    #[track_caller]
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "cannot combine a {}x{} grid with a {}x{} one",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    /// Moves all the cells `n` columns to the left, the ones going past the
+    /// left side are lost
+    pub fn shift_left(&mut self, n: usize) {
+        let (words, bits) = (n / 64, n % 64);
+        for row in self.words.chunks_mut(self.row_words.max(1)) {
+            for i in 0..row.len() {
+                let low = row.get(i + words).copied().unwrap_or(0);
+                let high = row.get(i + words + 1).copied().unwrap_or(0);
+                row[i] = match bits {
+                    0 => low,
+                    _ => low >> bits | high << (64 - bits),
+                };
+            }
+        }
+    }
+
+    /// Moves all the cells `n` columns to the right, the ones going past the
+    /// right side are lost
+    pub fn shift_right(&mut self, n: usize) {
+        let (words, bits) = (n / 64, n % 64);
+        let last_bits = self.width % 64;
+        for row in self.words.chunks_mut(self.row_words.max(1)) {
+            for i in (0..row.len()).rev() {
+                let high = i.checked_sub(words).map_or(0, |j| row[j]);
+                let low = i.checked_sub(words + 1).map_or(0, |j| row[j]);
+                row[i] = match bits {
+                    0 => high,
+                    _ => high << bits | low >> (64 - bits),
+                };
+            }
+            if last_bits != 0 {
+                if let Some(last) = row.last_mut() {
+                    *last &= (1 << last_bits) - 1;
+                }
+            }
+        }
+    }
+
+    /// Moves all the rows `n` rows up, the ones going past the top are lost
+    pub fn shift_up(&mut self, n: usize) {
+        let start = n.saturating_mul(self.row_words).min(self.words.len());
+        self.words.copy_within(start.., 0);
+        let len = self.words.len();
+        self.words[len - start..].fill(0);
+    }
+
+    /// Moves all the rows `n` rows down, the ones going past the bottom are
+    /// lost
+    pub fn shift_down(&mut self, n: usize) {
+        let start = n.saturating_mul(self.row_words).min(self.words.len());
+        let len = self.words.len();
+        self.words.copy_within(..len - start, start);
+        self.words[..start].fill(0);
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for (y, row) in grid.rows().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &set)| set) {
+                bits.set((x, y), true);
+            }
+        }
+        bits
+    }
+}
+
+///
+/// Using any coordinates to check if a cell is set. Cells out of the grid
+/// are not
+///
+impl<C: Coord> Index<C> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, coord: C) -> &Self::Output {
+        match self.get(coord) {
+            true => &true,
+            false => &false,
+        }
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.union_with(other);
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.intersect_with(other);
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        let mut union = self.clone();
+        union.union_with(other);
+        union
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        let mut intersection = self.clone();
+        intersection.intersect_with(other);
+        intersection
+    }
+}
+
+/// Prints the grid like `display_grid_with_bool`, with x for the cells that
+/// are set
+impl std::fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                match self.get((x, y)) {
+                    true => write!(f, "x")?,
+                    false => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_grid() {
+        let mut visited = BitGrid::new(70, 3);
+        assert!(visited.insert((0, 0)));
+        assert!(!visited.insert((0, 0)));
+        visited.set((69, 1), true);
+        visited.set((63, 2), true);
+        assert!(visited[(69, 1)] && !visited[(70, 1)] && !visited[(-1, 0)]);
+        assert_eq!(visited.count(), 3);
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            vec![(0, 0), (69, 1), (63, 2)]
+        );
+
+        let mut shifted = visited.clone();
+        shifted.shift_right(1);
+        assert_eq!(shifted.iter().collect::<Vec<_>>(), vec![(1, 0), (64, 2)]);
+        shifted.shift_left(64);
+        assert_eq!(shifted.iter().collect::<Vec<_>>(), vec![(0, 2)]);
+        shifted.shift_up(2);
+        assert_eq!(shifted.iter().collect::<Vec<_>>(), vec![(0, 0)]);
+        shifted.shift_down(1);
+        assert_eq!(shifted.iter().collect::<Vec<_>>(), vec![(0, 1)]);
+
+        assert_eq!((&visited | &shifted).count(), 4);
+        assert_eq!((&visited & &shifted).count(), 0);
+        visited &= &BitGrid::new(70, 3);
+        assert_eq!(visited.count(), 0);
+
+        let mut grid = Grid::new(3, 2, false);
+        grid[(1, 0)] = true;
+        grid[(2, 1)] = true;
+        assert_eq!(BitGrid::from(&grid).to_string(), ".x.\n..x\n");
+    }
+}
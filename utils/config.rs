@@ -0,0 +1,369 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::encryption::Key;
+
+/// Name of the configuration file, both in the workspace root and in the
+/// user configuration directory
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+/// Where this crate was built, used as the workspace root when it cannot be
+/// found from the current directory, see [`workspace_root`]
+pub const WORKSPACE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Input folder used when nothing is configured, relative to the workspace
+/// root. `{year}` is replaced with the year being solved.
+pub const DEFAULT_INPUT_DIR: &str = "aoc{year}/src/input";
+
+/// The workspace root, where the project configuration file is looked up.
+/// This is the closest folder above the current directory holding an
+/// `aoc.toml` or a `Cargo.toml` with a `[workspace]`, else [`WORKSPACE_ROOT`].
+pub fn workspace_root() -> PathBuf {
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| {
+            dir.ancestors()
+                .find(|d| is_workspace_root(d))
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| PathBuf::from(WORKSPACE_ROOT))
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    dir.join(CONFIG_FILE_NAME).is_file()
+        || std::fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|manifest| manifest.parse::<toml::Table>().ok())
+            .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+/// How the runners print the results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per day
+    Json,
+}
+
+/// Errors that can happen when loading the configuration
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A configuration file exists but could not be read
+    Io(PathBuf, std::io::Error),
+    /// A configuration file is not valid TOML, or has unexpected keys
+    Parse(PathBuf, toml::de::Error),
+    /// An environment variable has a value that cannot be used
+    Env(&'static str, String),
+    /// The input encryption key is malformed
+    Key(super::encryption::EncryptionError),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "cannot read {}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => write!(f, "invalid config {}: {}", path.display(), e),
+            ConfigError::Env(name, value) => write!(f, "invalid value for {}: {:?}", name, value),
+            ConfigError::Key(e) => write!(f, "invalid key: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// Command line flags overriding the configuration files.
+// Meant to be flattened into the arguments of a runner:
+//
+// #[derive(clap::Parser)]
+// struct Args {
+//     #[command(flatten)]
+//     config: aoc_utils::config::ConfigArgs,
+// }
+//
+// Not a doc comment, as clap would print it in the help of the runners
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ConfigArgs {
+    /// Folder containing the puzzle inputs, `{year}` is replaced by the year
+    #[arg(long)]
+    pub input_dir: Option<PathBuf>,
+
+    /// Session cookie used to download the inputs
+    #[arg(long)]
+    pub session: Option<String>,
+
+    /// How results are printed
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Compare the results with the expected answers
+    #[arg(long, overrides_with = "no_check")]
+    pub check: bool,
+
+    /// Do not compare the results, even if the configuration says so
+    #[arg(long, overrides_with = "check")]
+    pub no_check: bool,
+
+    /// Give up on a day after this many seconds, and on the days after it
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Solve every input of the days, including the ones from other accounts,
+    /// and report the ones failing
+    #[arg(long, overrides_with = "no_all_inputs")]
+    pub all_inputs: bool,
+
+    /// Only solve the main input, even if the configuration says otherwise
+    #[arg(long, overrides_with = "all_inputs")]
+    pub no_all_inputs: bool,
+
+    /// Print the debug logs of the solutions, twice for trace logs
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+/// One source of configuration: a file, the environment or the command line.
+/// Every value is optional, the layers get merged by order of precedence
+///
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigLayer {
+    input_dir: Option<PathBuf>,
+    session: Option<String>,
+    key: Option<String>,
+    year: Option<u16>,
+    output: Option<OutputFormat>,
+    check: Option<bool>,
+    timeout: Option<u64>,
+    all_inputs: Option<bool>,
+    log: Option<String>,
+}
+
+impl ConfigLayer {
+    /// Reads a layer from a TOML file. Missing files are just an empty layer.
+    /// Relative input folders are resolved against the folder of the file.
+    fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(ConfigError::Io(path.to_path_buf(), e)),
+        };
+
+        let mut layer: Self =
+            toml::from_str(&content).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+        if let (Some(dir), Some(parent)) = (&layer.input_dir, path.parent()) {
+            layer.input_dir = Some(parent.join(dir));
+        }
+        Ok(layer)
+    }
+
+    /// Reads a layer from the `AOC_*` environment variables
+    fn from_env() -> Result<Self, ConfigError> {
+        fn var(name: &str) -> Option<String> {
+            std::env::var(name).ok().filter(|v| !v.is_empty())
+        }
+        fn parse<T: std::str::FromStr>(name: &'static str) -> Result<Option<T>, ConfigError> {
+            var(name)
+                .map(|v| v.parse().map_err(|_| ConfigError::Env(name, v)))
+                .transpose()
+        }
+
+        let output = match var("AOC_OUTPUT").as_deref() {
+            None => None,
+            Some("text") => Some(OutputFormat::Text),
+            Some("json") => Some(OutputFormat::Json),
+            Some(other) => return Err(ConfigError::Env("AOC_OUTPUT", other.to_string())),
+        };
+
+        Ok(Self {
+            input_dir: var("AOC_INPUT_DIR").map(PathBuf::from),
+            session: var("AOC_SESSION"),
+            key: var("AOC_KEY"),
+            year: parse("AOC_YEAR")?,
+            output,
+            check: parse("AOC_CHECK")?,
+            timeout: parse("AOC_TIMEOUT")?,
+            all_inputs: parse("AOC_ALL_INPUTS")?,
+            log: var("AOC_LOG"),
+        })
+    }
+
+    /// Reads a layer from the command line flags
+    fn from_args(args: &ConfigArgs) -> Self {
+        Self {
+            input_dir: args.input_dir.clone(),
+            session: args.session.clone(),
+            key: None,
+            year: None,
+            output: args.output,
+            check: flag(args.check, args.no_check),
+            timeout: args.timeout,
+            all_inputs: flag(args.all_inputs, args.no_all_inputs),
+            log: None,
+        }
+    }
+
+    /// Keeps the values of self, and fills the blanks with the ones from
+    /// the lower precedence layer
+    fn or(self, lower: Self) -> Self {
+        Self {
+            input_dir: self.input_dir.or(lower.input_dir),
+            session: self.session.or(lower.session),
+            key: self.key.or(lower.key),
+            year: self.year.or(lower.year),
+            output: self.output.or(lower.output),
+            check: self.check.or(lower.check),
+            timeout: self.timeout.or(lower.timeout),
+            all_inputs: self.all_inputs.or(lower.all_inputs),
+            log: self.log.or(lower.log),
+        }
+    }
+}
+
+/// Value of a switch with `--flag` and `--no-flag` options, if one of them
+/// was given
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Settings shared by the runners and the `aoc` tool.
+///
+/// They are looked up in this order, the first one defining a value wins:
+/// 1. Command line flags
+/// 2. `AOC_INPUT_DIR`, `AOC_SESSION`, `AOC_KEY`, `AOC_YEAR`, `AOC_OUTPUT`,
+///    `AOC_CHECK`, `AOC_TIMEOUT`, `AOC_ALL_INPUTS` and `AOC_LOG` environment
+///    variables
+/// 3. `aoc.toml` in the workspace root
+/// 4. `aoc.toml` in the user configuration directory
+///    (`$XDG_CONFIG_HOME/aoc/` or `~/.config/aoc/`)
+///
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Folder containing the puzzle inputs, may contain `{year}`
+    pub input_dir: PathBuf,
+    /// Session cookie from adventofcode.com, needed to download inputs
+    pub session: Option<String>,
+    /// Key used to decrypt the `.enc` inputs
+    pub key: Option<Key>,
+    /// Year used when a command does not specify one
+    pub year: Option<u16>,
+    pub output: OutputFormat,
+    /// Compare results with the answers stored next to the inputs
+    pub check: bool,
+    /// Maximum time to spend on a day. The solution is not killed then, it
+    /// keeps running in the background, so the runner skips the remaining
+    /// days instead of solving them next to it
+    pub timeout: Option<Duration>,
+    /// Solve the inputs from other accounts as well, see
+    /// [`crate::input::named_inputs`]
+    pub all_inputs: bool,
+    /// Number of `-v` flags, only from the command line
+    pub verbosity: u8,
+    /// Log filters per target, see [`crate::logging::init`]
+    pub log: Option<String>,
+}
+
+impl Config {
+    /// Loads the configuration from all the sources, see [`Config`]
+    pub fn load(args: &ConfigArgs) -> Result<Self, ConfigError> {
+        let mut layer = ConfigLayer::from_args(args).or(ConfigLayer::from_env()?);
+        for path in Self::files() {
+            layer = layer.or(ConfigLayer::from_file(&path)?);
+        }
+        let mut config = Self::from_layer(layer)?;
+        config.verbosity = args.verbose;
+        Ok(config)
+    }
+
+    /// Configuration files, from the highest to the lowest precedence
+    pub fn files() -> Vec<PathBuf> {
+        let mut files = vec![workspace_root().join(CONFIG_FILE_NAME)];
+        let user_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+        if let Some(dir) = user_dir {
+            files.push(dir.join("aoc").join(CONFIG_FILE_NAME));
+        }
+        files
+    }
+
+    /// Input folder for a given year
+    pub fn input_dir(&self, year: u16) -> PathBuf {
+        PathBuf::from(
+            self.input_dir
+                .to_string_lossy()
+                .replace("{year}", &year.to_string()),
+        )
+    }
+
+    fn from_layer(layer: ConfigLayer) -> Result<Self, ConfigError> {
+        let key = layer
+            .key
+            .map(|k| k.parse().map_err(ConfigError::Key))
+            .transpose()?;
+
+        Ok(Self {
+            input_dir: layer
+                .input_dir
+                .unwrap_or_else(|| workspace_root().join(DEFAULT_INPUT_DIR)),
+            session: layer.session,
+            key,
+            year: layer.year,
+            output: layer.output.unwrap_or_default(),
+            check: layer.check.unwrap_or(false),
+            timeout: layer.timeout.filter(|&t| t > 0).map(Duration::from_secs),
+            all_inputs: layer.all_inputs.unwrap_or(false),
+            verbosity: 0,
+            log: layer.log,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_precedence() {
+        let file: ConfigLayer = toml::from_str(
+            r#"
+            input_dir = "inputs/{year}"
+            session = "from-file"
+            output = "json"
+            timeout = 10
+            "#,
+        )
+        .unwrap();
+        let args = ConfigArgs {
+            session: Some("from-args".to_string()),
+            check: true,
+            ..Default::default()
+        };
+
+        let config = Config::from_layer(ConfigLayer::from_args(&args).or(file)).unwrap();
+
+        assert_eq!(config.session.as_deref(), Some("from-args"));
+        assert_eq!(config.output, OutputFormat::Json);
+        assert!(config.check);
+        assert_eq!(config.timeout, Some(Duration::from_secs(10)));
+        assert_eq!(config.input_dir(2024), PathBuf::from("inputs/2024"));
+        assert!(toml::from_str::<ConfigLayer>("unknown = 1").is_err());
+
+        let file: ConfigLayer = toml::from_str("check = true").unwrap();
+        let args = ConfigArgs {
+            no_check: true,
+            ..Default::default()
+        };
+        let config = Config::from_layer(ConfigLayer::from_args(&args).or(file)).unwrap();
+        assert!(!config.check);
+    }
+}
@@ -2,7 +2,7 @@ use super::direction::Direction;
 use super::point::Point;
 
 /// Represents a point with a direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DirectedPoint {
     pub point: Point,
     pub direction: Direction,
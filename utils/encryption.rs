@@ -0,0 +1,136 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+/// Extension added to the name of encrypted files, e.g. `day05.txt.enc`
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Header of encrypted files, so we can change the format later
+const MAGIC: &[u8] = b"AOC1";
+
+/// Length of the nonce, stored after the header
+const NONCE_LENGTH: usize = 24;
+
+/// Errors when dealing with encrypted inputs
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncryptionError {
+    /// The key is not 64 hexadecimal characters
+    InvalidKey,
+    /// The data is not an encrypted input, was encrypted with another key or
+    /// has been tampered with
+    InvalidData,
+}
+
+impl std::fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::InvalidKey => write!(f, "key should be 64 hexadecimal characters"),
+            EncryptionError::InvalidData => write!(f, "cannot decrypt, wrong key or corrupt file"),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+/// Key used to encrypt the inputs, shared between the members of a team.
+/// It is written as 64 hexadecimal characters in the configuration.
+///
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Creates a new random key
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Encrypts some data. The output contains everything needed to decrypt it
+    /// back with the same key
+    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        let cipher = XChaCha20Poly1305::new(&self.0.into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let encrypted = cipher
+            .encrypt(&nonce, data)
+            .expect("Encryption of in-memory data should not fail");
+
+        let mut output = Vec::with_capacity(MAGIC.len() + NONCE_LENGTH + encrypted.len());
+        output.extend_from_slice(MAGIC);
+        output.extend_from_slice(&nonce);
+        output.extend(encrypted);
+        output
+    }
+
+    /// Decrypts data produced by [`Key::encrypt`]
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .filter(|d| d.len() >= NONCE_LENGTH)
+            .ok_or(EncryptionError::InvalidData)?;
+        let (nonce, encrypted) = data.split_at(NONCE_LENGTH);
+
+        XChaCha20Poly1305::new(&self.0.into())
+            .decrypt(XNonce::from_slice(nonce), encrypted)
+            .map_err(|_| EncryptionError::InvalidData)
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = EncryptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(EncryptionError::InvalidKey);
+        }
+
+        let mut key = [0; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+                .map_err(|_| EncryptionError::InvalidKey)?;
+        }
+        Ok(Self(key))
+    }
+}
+
+/// Prints the key in hexadecimal, as expected in the configuration
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Keep the key out of debug prints and logs
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_encryption_round_trip() {
+        let key = Key::generate();
+        let input = b"3   4\n4   3\n2   5\n";
+
+        let encrypted = key.encrypt(input);
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), input);
+
+        // Key is written and read back as hex
+        assert_eq!(Key::from_str(&key.to_string()).as_ref(), Ok(&key));
+        assert_eq!(Key::from_str("abcd"), Err(EncryptionError::InvalidKey));
+
+        // Another key, or tampered data, is refused
+        let other = Key::generate();
+        assert_eq!(other.decrypt(&encrypted), Err(EncryptionError::InvalidData));
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(key.decrypt(&tampered), Err(EncryptionError::InvalidData));
+    }
+}
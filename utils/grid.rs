@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
+use super::direction::Direction;
 use super::point::Point;
 
 /// We often need to make a 2 dimensional grid of some sort.
@@ -43,7 +45,7 @@ impl<T: Copy> Grid<T> {
     ///
     pub fn from_slice(width: usize, height: usize, data: &[T]) -> Self {
         assert!(
-            width * height >= data.len(),
+            data.len() >= width * height,
             "Data is too small for height/width"
         );
         Self {
@@ -59,26 +61,24 @@ impl<T: Copy> Grid<T> {
         x >= 0 && (x as usize) < self.width && y >= 0 && (y as usize) < self.height
     }
 
-    pub fn neighbors(&self, point: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
-        let point = Point::new(point.0 as i32, point.1 as i32);
-        let mut neighbors = Vec::with_capacity(8);
-        for &offset in &[
-            Point::new(-1, -1),
-            Point::new(-1, 0),
-            Point::new(-1, 1),
-            Point::new(0, -1),
-            Point::new(0, 1),
-            Point::new(1, -1),
-            Point::new(1, 0),
-            Point::new(1, 1),
-        ] {
-            let neighbor_point = point + offset;
-            if self.point_within_bounds((neighbor_point.x as isize, neighbor_point.y as isize)) {
-                neighbors.push((neighbor_point.x as usize, neighbor_point.y as usize));
-            }
-        }
+    /// Positions of the neighbors of a cell, diagonals included
+    pub fn neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ALL_AROUND
+            .into_iter()
+            .filter(move |&(dx, dy)| around.contains(dx, dy))
+            .map(move |(dx, dy)| offset(point, dx, dy))
+    }
 
-        neighbors.into_iter()
+    /// Positions of the neighbors of a cell, without the diagonals
+    pub fn orthogonal_neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ORTHOGONAL
+            .into_iter()
+            .filter(move |&(_, dx, dy)| around.contains(dx, dy))
+            .map(move |(_, dx, dy)| offset(point, dx, dy))
     }
 
     /// Checks if a coordinate tuple is within bounds of the grid
@@ -97,6 +97,345 @@ impl<T: Copy> Grid<T> {
     }
 }
 
+impl<T> Grid<T> {
+    /// Neighbors of a cell without the diagonals, with the direction they are
+    /// in, their position and their value
+    pub fn orthogonal_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Direction, Point, &T)> + '_ {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ORTHOGONAL
+            .into_iter()
+            .filter(move |&(_, dx, dy)| around.contains(dx, dy))
+            .map(move |(direction, dx, dy)| {
+                let (x, y) = offset(point, dx, dy);
+                (direction, position(x, y), &self.data[y * self.width + x])
+            })
+    }
+
+    /// Same as `orthogonal_cells`, with mutable values
+    pub fn orthogonal_cells_mut(
+        &mut self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Direction, Point, &mut T)> + '_ {
+        let width = self.width;
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        let mut cells = self.data.iter_mut();
+        let mut next = 0;
+        ORTHOGONAL
+            .into_iter()
+            .filter(move |&(_, dx, dy)| around.contains(dx, dy))
+            .map(move |(direction, dx, dy)| {
+                let (x, y) = offset(point, dx, dy);
+                let index = y * width + x;
+                // Neighbors come in the order they are stored, each one after the previous
+                let cell = cells
+                    .nth(index - next)
+                    .expect("neighbors are within bounds");
+                next = index + 1;
+                (direction, position(x, y), cell)
+            })
+    }
+
+    /// Neighbors of a cell, diagonals included, with the offset they are at,
+    /// e.g. `(-1, 1)` for the bottom left one, their position and their value
+    pub fn neighbor_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Point, Point, &T)> + '_ {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ALL_AROUND
+            .into_iter()
+            .filter(move |&(dx, dy)| around.contains(dx, dy))
+            .map(move |(dx, dy)| {
+                let (x, y) = offset(point, dx, dy);
+                let delta = Point::new(dx as i32, dy as i32);
+                (delta, position(x, y), &self.data[y * self.width + x])
+            })
+    }
+
+    /// Same as `neighbor_cells`, with mutable values
+    pub fn neighbor_cells_mut(
+        &mut self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Point, Point, &mut T)> + '_ {
+        let width = self.width;
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        let mut cells = self.data.iter_mut();
+        let mut next = 0;
+        ALL_AROUND
+            .into_iter()
+            .filter(move |&(dx, dy)| around.contains(dx, dy))
+            .map(move |(dx, dy)| {
+                let (x, y) = offset(point, dx, dy);
+                let index = y * width + x;
+                // Neighbors come in the order they are stored, each one after the previous
+                let cell = cells
+                    .nth(index - next)
+                    .expect("neighbors are within bounds");
+                next = index + 1;
+                (Point::new(dx as i32, dy as i32), position(x, y), cell)
+            })
+    }
+}
+
+/// Offsets of the neighbors of a cell without the diagonals, in the order
+/// they are stored in the grid
+pub(crate) const ORTHOGONAL: [(Direction, isize, isize); 4] = [
+    (Direction::Up, 0, -1),
+    (Direction::Left, -1, 0),
+    (Direction::Right, 1, 0),
+    (Direction::Down, 0, 1),
+];
+
+/// Offsets of the neighbors of a cell, diagonals included, in the order they
+/// are stored in the grid
+pub(crate) const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Sides of a cell that have neighbors in the grid, so that the bounds are
+/// checked once for all the neighbors. A cell out of the grid, e.g. with
+/// negative coordinates, has none
+#[derive(Clone, Copy)]
+pub(crate) struct Around {
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+}
+
+impl Around {
+    pub(crate) fn new(width: usize, height: usize, point: impl Coord) -> Self {
+        let (x, y) = point.xy().unwrap_or((usize::MAX, usize::MAX));
+        let inside = x < width && y < height;
+        Self {
+            left: inside && x > 0,
+            right: inside && x + 1 < width,
+            up: inside && y > 0,
+            down: inside && y + 1 < height,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, dx: isize, dy: isize) -> bool {
+        (dx >= 0 || self.left)
+            && (dx <= 0 || self.right)
+            && (dy >= 0 || self.up)
+            && (dy <= 0 || self.down)
+    }
+}
+
+#[inline]
+pub(crate) fn offset((x, y): (usize, usize), dx: isize, dy: isize) -> (usize, usize) {
+    (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+}
+
+#[inline]
+pub(crate) fn position(x: usize, y: usize) -> Point {
+    Point::new(x as i32, y as i32)
+}
+
+impl<T> Grid<T> {
+    /// Rows of the grid, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// Same as `rows`, with mutable values
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        self.data.chunks_mut(self.width.max(1))
+    }
+
+    /// Columns of the grid, from left to right, each from top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Same as `columns`, with mutable values
+    pub fn columns_mut(&mut self) -> impl Iterator<Item = impl Iterator<Item = &mut T> + '_> + '_ {
+        let width = self.width;
+        self.lines_mut(width, |x, _| x)
+    }
+
+    /// Values of a column, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.data[self.column_start(x)..]
+            .iter()
+            .step_by(self.width.max(1))
+    }
+
+    /// Same as `column`, with mutable values
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> + '_ {
+        let width = self.width.max(1);
+        let start = self.column_start(x);
+        self.data[start..].iter_mut().step_by(width)
+    }
+
+    /// Index of the top of a column, the end of the data if it is out of
+    /// the grid
+    fn column_start(&self, x: usize) -> usize {
+        match x < self.width {
+            true => x,
+            false => self.data.len(),
+        }
+    }
+
+    /// Diagonals of the grid going down to the right, starting from the one
+    /// in the bottom left corner up to the one in the top right corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width, self.height);
+        (0..(width + height).saturating_sub(1)).map(move |k| {
+            let start = match k < height {
+                true => (0, height - 1 - k),
+                false => (k + 1 - height, 0),
+            };
+            self.ray(start, Point::new(1, 1)).map(|(_, value)| value)
+        })
+    }
+
+    /// Same as `diagonals`, with mutable values
+    pub fn diagonals_mut(
+        &mut self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &mut T> + '_> + '_ {
+        let (width, height) = (self.width, self.height);
+        self.lines_mut((width + height).saturating_sub(1), move |x, y| {
+            x + height - 1 - y
+        })
+    }
+
+    /// Diagonals of the grid going down to the left, starting from the one
+    /// in the top left corner up to the one in the bottom right corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width, self.height);
+        (0..(width + height).saturating_sub(1)).map(move |k| {
+            let start = match k < width {
+                true => (k, 0),
+                false => (width - 1, k + 1 - width),
+            };
+            self.ray(start, Point::new(-1, 1)).map(|(_, value)| value)
+        })
+    }
+
+    /// Same as `anti_diagonals`, with mutable values
+    pub fn anti_diagonals_mut(
+        &mut self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &mut T> + '_> + '_ {
+        let (width, height) = (self.width, self.height);
+        self.lines_mut((width + height).saturating_sub(1), |x, y| x + y)
+    }
+
+    /// Splits the cells into `count` lines, `line` giving the line of a cell
+    /// from its position. The mutable borrows of the lines cannot overlap, so
+    /// the cells are handed out once, in storage order, which is from top to
+    /// bottom within each line
+    fn lines_mut(
+        &mut self,
+        count: usize,
+        line: impl Fn(usize, usize) -> usize,
+    ) -> impl Iterator<Item = impl Iterator<Item = &mut T> + '_> + '_ {
+        let width = self.width.max(1);
+        let mut lines: Vec<Vec<&mut T>> = (0..count).map(|_| Vec::new()).collect();
+        for (index, cell) in self.data.iter_mut().enumerate() {
+            lines[line(index % width, index / width)].push(cell);
+        }
+        lines.into_iter().map(Vec::into_iter)
+    }
+
+    /// Cells from a position, included, stepping in a direction until the
+    /// edge of the grid. The step is a `Direction`, or a `Point` to go in
+    /// diagonal, e.g. `Point::new(1, -1)` to go up to the right
+    pub fn ray(
+        &self,
+        from: impl Coord,
+        step: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let step = step.into();
+        let length = self.ray_length(from, step);
+        let from = from.xy().map_or(Point::new(0, 0), |(x, y)| position(x, y));
+        (0..length as i32).map(move |i| {
+            let point = Point::new(from.x + i * step.x, from.y + i * step.y);
+            (point, &self[point])
+        })
+    }
+
+    /// Same as `ray`, with mutable values
+    pub fn ray_mut(
+        &mut self,
+        from: impl Coord,
+        step: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &mut T)> + '_ {
+        let step = step.into();
+        let mut remaining = self.ray_length(from, step);
+        let from = from.xy().map_or(Point::new(0, 0), |(x, y)| position(x, y));
+        let start = from.y as usize * self.width + from.x as usize;
+        let stride = step.y as isize * self.width as isize + step.x as isize;
+
+        // Going forward, the cells of the ray are at the start of `rest`,
+        // going backward at its end
+        let len = self.data.len();
+        let mut rest: &mut [T] = match stride > 0 {
+            true => &mut self.data[start.min(len)..],
+            false => &mut self.data[..(start + 1).min(len)],
+        };
+        let mut point = from;
+        std::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            remaining -= 1;
+            let skip = stride.unsigned_abs().saturating_sub(1);
+            let cell = match stride > 0 {
+                true => {
+                    let (cell, after) = std::mem::take(&mut rest).split_first_mut()?;
+                    rest = after.get_mut(skip.min(after.len())..).unwrap_or_default();
+                    cell
+                }
+                false => {
+                    let (cell, before) = std::mem::take(&mut rest).split_last_mut()?;
+                    let end = before.len().saturating_sub(skip);
+                    rest = &mut before[..end];
+                    cell
+                }
+            };
+            let current = point;
+            point += step;
+            Some((current, cell))
+        })
+    }
+
+    /// Number of cells of a ray within the grid
+    fn ray_length(&self, from: impl Coord, step: Point) -> usize {
+        ray_length(self.width, self.height, from, step)
+    }
+}
+
+/// Number of cells of a ray within a rectangle of the given size
+pub(crate) fn ray_length(width: usize, height: usize, from: impl Coord, step: Point) -> usize {
+    assert!(step != Point::new(0, 0), "a ray needs a step to move");
+    let Some((x, y)) = from.xy().filter(|&(x, y)| x < width && y < height) else {
+        return 0;
+    };
+    let along = |position: usize, size: usize, step: i32| match step {
+        0 => usize::MAX,
+        s if s > 0 => (size - 1 - position) / s as usize + 1,
+        s => position / s.unsigned_abs() as usize + 1,
+    };
+    along(x, width, step.x).min(along(y, height, step.y))
+}
+
 impl<T: Copy + PartialEq> Grid<T> {
     /// Finds the first element in the grid that is equal
     /// to the value passed in parameter
@@ -142,6 +481,98 @@ where
     }
 }
 
+impl<T: Clone> Grid<T> {
+    /// Builds a grid of the given size, the value of each cell coming from
+    /// its coordinates
+    fn from_fn(width: usize, height: usize, cell: impl Fn(usize, usize) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Swaps rows and columns, the top right corner going to the bottom left
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[x * self.width + y].clone()
+        })
+    }
+
+    /// Rotates the grid clockwise, the top row becoming the right column
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[(self.height - 1 - x) * self.width + y].clone()
+        })
+    }
+
+    /// Rotates the grid counter-clockwise, the top row becoming the left
+    /// column
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[x * self.width + self.width - 1 - y].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let mut grid = self.clone();
+        grid.flip_horizontal_in_place();
+        grid
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let mut grid = self.clone();
+        grid.flip_vertical_in_place();
+        grid
+    }
+}
+
+impl<T> Grid<T> {
+    /// Same as `transpose`, without allocating. Only for square grids
+    pub fn transpose_in_place(&mut self) {
+        assert_eq!(
+            self.width, self.height,
+            "only square grids can be transposed in place"
+        );
+        for y in 0..self.height {
+            for x in y + 1..self.width {
+                self.data.swap(y * self.width + x, x * self.width + y);
+            }
+        }
+    }
+
+    /// Same as `rotate_cw`, without allocating. Only for square grids
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_horizontal_in_place();
+    }
+
+    /// Same as `rotate_ccw`, without allocating. Only for square grids
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_vertical_in_place();
+    }
+
+    /// Same as `flip_horizontal`, without allocating
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.rows_mut().for_each(|row| row.reverse());
+    }
+
+    /// Same as `flip_vertical`, without allocating
+    pub fn flip_vertical_in_place(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+}
+
 impl<T: std::fmt::Display + Copy> Grid<Option<T>> {
     /// Create a new grid with the indicated dimensions, and filled with the default value
     ///
@@ -174,125 +605,335 @@ impl Grid<bool> {
     }
 }
 
-///
-/// Using a `usize` tuple to lookup a value in the grid
-///
-impl<T> Index<(usize, usize)> for Grid<T> {
-    type Output = T;
+/// Coordinates of a cell in a grid: `(x, y)` tuples of `usize`, `isize` or
+/// `i32`, or a `Point`
+pub trait Coord: Copy + std::fmt::Debug {
+    /// The coordinates as `(x, y)`, if none of them is negative
+    fn xy(self) -> Option<(usize, usize)>;
+}
 
-    /// x is based on the width and y on the height
-    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        &self.data[y * self.width + x]
+impl Coord for (usize, usize) {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        Some(self)
     }
 }
 
-///
-/// Using a `isize` tuple to lookup a value in the grid
-///
-impl<T> Index<(isize, isize)> for Grid<T> {
-    type Output = T;
+impl Coord for (isize, isize) {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.0).ok()?, usize::try_from(self.1).ok()?))
+    }
+}
 
-    fn index(&self, (x, y): (isize, isize)) -> &Self::Output {
-        &self.data[y as usize * self.width + x as usize]
+impl Coord for (i32, i32) {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.0).ok()?, usize::try_from(self.1).ok()?))
     }
 }
 
-///
-/// Using a `i32` tuple to lookup a value in the grid
-///
-impl<T> Index<(i32, i32)> for Grid<T> {
-    type Output = T;
+impl Coord for Point {
+    #[inline]
+    fn xy(self) -> Option<(usize, usize)> {
+        self.as_i32_tuple().xy()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Index of a cell in the data, if it is within the grid
+    #[inline]
+    fn index_of(&self, coord: impl Coord) -> Option<usize> {
+        let (x, y) = coord.xy()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Value of a cell, or `None` if it is out of the grid
+    #[inline]
+    pub fn get(&self, coord: impl Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.data[i])
+    }
 
-    fn index(&self, (x, y): (i32, i32)) -> &Self::Output {
-        &self.data[y as usize * self.width + x as usize]
+    /// Mutable value of a cell, or `None` if it is out of the grid
+    #[inline]
+    pub fn get_mut(&mut self, coord: impl Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.data[i])
+    }
+
+    #[track_caller]
+    fn index_or_panic(&self, coord: impl Coord) -> usize {
+        match self.index_of(coord) {
+            Some(i) => i,
+            None => panic!(
+                "{:?} is out of the grid, which is {} wide and {} high",
+                coord, self.width, self.height
+            ),
+        }
     }
 }
 
 ///
-/// Using a Point (x,y) to lookup a value in the grid
+/// Using any coordinates to lookup a value in the grid, x is based on the
+/// width and y on the height. Panics out of the grid, see `get` otherwise
 ///
-impl<T> Index<Point> for Grid<T> {
+impl<T, C: Coord> Index<C> for Grid<T> {
     type Output = T;
 
-    fn index(&self, point: Point) -> &Self::Output {
-        &self.data[point.y as usize * self.width + point.x as usize]
+    #[track_caller]
+    fn index(&self, coord: C) -> &Self::Output {
+        &self.data[self.index_or_panic(coord)]
     }
 }
 
 ///
-/// Using a `usize` tuple to set a value in the grid
+/// Using any coordinates to set a value in the grid
 ///
-impl<T> IndexMut<(usize, usize)> for Grid<T> {
-    /// x is based on the width and y on the height
-    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        &mut self.data[y * self.width + x]
+impl<T, C: Coord> IndexMut<C> for Grid<T> {
+    #[track_caller]
+    fn index_mut(&mut self, coord: C) -> &mut Self::Output {
+        let i = self.index_or_panic(coord);
+        &mut self.data[i]
     }
 }
 
-///
-/// Using a `isize` tuple to set a value in the grid
-///
-impl<T> IndexMut<(isize, isize)> for Grid<T> {
-    fn index_mut(&mut self, (x, y): (isize, isize)) -> &mut Self::Output {
-        &mut self.data[y as usize * self.width + x as usize]
+/// Errors that can happen when parsing a grid from the input. `E` is the
+/// error given for the characters that are not cells of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridParseError<E = InvalidCell> {
+    /// There is no row at all
+    Empty,
+    /// A row does not have the same width as the first one. Like in the other
+    /// errors, `line` is the line of the input, counting from 1 and including
+    /// the blank lines before the grid
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character is not one of the cells of the grid. `column` counts from 1
+    InvalidCell {
+        line: usize,
+        column: usize,
+        error: E,
+    },
+    /// A marker that should be there once is there again
+    DuplicateMarker {
+        marker: char,
+        line: usize,
+        column: usize,
+    },
+    /// A marker that should be there once is not there
+    MissingMarker { marker: char },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for GridParseError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "the grid is empty"),
+            GridParseError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {} wide, expected {} like the first row",
+                line, found, expected
+            ),
+            GridParseError::InvalidCell {
+                line,
+                column,
+                error,
+            } => write!(f, "{} at line {}, column {}", error, line, column),
+            GridParseError::DuplicateMarker {
+                marker,
+                line,
+                column,
+            } => write!(
+                f,
+                "{:?} is there twice, again at line {}, column {}",
+                marker, line, column
+            ),
+            GridParseError::MissingMarker { marker } => {
+                write!(f, "{:?} is not in the grid", marker)
+            }
+        }
     }
 }
 
-///
-/// Using a `i32` tuple to set a value in the grid
-///
-impl<T> IndexMut<(i32, i32)> for Grid<T> {
-    fn index_mut(&mut self, (x, y): (i32, i32)) -> &mut Self::Output {
-        &mut self.data[y as usize * self.width + x as usize]
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for GridParseError<E> {}
+
+impl<T> Grid<T> {
+    /// Builds a grid from the rows of the input, `cells` pushing the cells of
+    /// a row, given its `y` and its line in the input. Blank lines before and
+    /// after the grid are ignored, and lines may end with `\n` or `\r\n`. All
+    /// the rows must have the same width
+    fn parse_rows<E>(
+        s: &str,
+        mut cells: impl FnMut(usize, usize, &str, &mut Vec<T>) -> Result<(), GridParseError<E>>,
+    ) -> Result<Self, GridParseError<E>> {
+        let lines: Vec<&str> = s.lines().collect();
+        let start = lines
+            .iter()
+            .position(|line| !line.trim().is_empty())
+            .ok_or(GridParseError::Empty)?;
+        let end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(start, |end| end + 1);
+
+        let mut data = Vec::new();
+        let mut width = 0;
+        for (y, line) in lines[start..end].iter().enumerate() {
+            let before = data.len();
+            cells(y, start + y + 1, line, &mut data)?;
+            let found = data.len() - before;
+            if y == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridParseError::Ragged {
+                    line: start + y + 1,
+                    expected: width,
+                    found,
+                });
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: end - start,
+            data,
+        })
+    }
+
+    /// Parses a grid, `cell` giving the cell of each character, e.g. for a
+    /// grid of digits:
+    ///
+    /// ```rust,ignore
+    /// let grid = Grid::parse_with(input, |c| c.to_digit(10).ok_or(InvalidCell(c)))?;
+    /// ```
+    pub fn parse_with<E>(
+        s: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridParseError<E>> {
+        Self::parse_rows(s, |_, line_number, line, data| {
+            for (x, c) in line.chars().enumerate() {
+                data.push(cell(c).map_err(|error| GridParseError::InvalidCell {
+                    line: line_number,
+                    column: x + 1,
+                    error,
+                })?);
+            }
+            Ok(())
+        })
     }
 }
 
-///
-/// Using a Point (x,y) to set a value in the grid
-///
-impl<T> IndexMut<Point> for Grid<T> {
-    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
-        &mut self.data[point.y as usize * self.width + point.x as usize]
+/// Positions of the markers taken out of a grid, see `parse_with_positions`
+pub type Markers = HashMap<char, (usize, usize)>;
+
+impl<T: Copy> Grid<T> {
+    /// Parses a grid like `parse_with`, also giving the positions of markers
+    /// that are there once, like the start `S` or the robot `@`. They are
+    /// replaced with the `floor` cell in the grid. Each marker must be there,
+    /// so that its position can be looked up directly:
+    ///
+    /// ```rust,ignore
+    /// let (grid, positions) = Grid::parse_with_positions(input, &['S', 'E'], Tile::Floor, Tile::try_from)?;
+    /// let start = positions[&'S'];
+    /// ```
+    pub fn parse_with_positions<E>(
+        s: &str,
+        markers: &[char],
+        floor: T,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<(Self, Markers), GridParseError<E>> {
+        let mut positions = HashMap::new();
+        let grid = Self::parse_rows(s, |y, line_number, line, data| {
+            for (x, c) in line.chars().enumerate() {
+                if !markers.contains(&c) {
+                    data.push(cell(c).map_err(|error| GridParseError::InvalidCell {
+                        line: line_number,
+                        column: x + 1,
+                        error,
+                    })?);
+                    continue;
+                }
+                if positions.insert(c, (x, y)).is_some() {
+                    return Err(GridParseError::DuplicateMarker {
+                        marker: c,
+                        line: line_number,
+                        column: x + 1,
+                    });
+                }
+                data.push(floor);
+            }
+            Ok(())
+        })?;
+        if let Some(&marker) = markers.iter().find(|m| !positions.contains_key(m)) {
+            return Err(GridParseError::MissingMarker { marker });
+        }
+        Ok((grid, positions))
     }
 }
 
 /// Derives a string into a char grid
-/// Could probably be more efficient with u8
-/// .... Maybe later
 impl FromStr for Grid<char> {
-    type Err = ();
+    type Err = GridParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let height = s.lines().filter(|line| !line.trim().is_empty()).count();
-        let width = s.lines().nth(0).ok_or(())?.len();
-        let data: Vec<char> = s.lines().flat_map(|l| l.chars()).collect();
+        Self::parse_rows(s, |_, _, line, data| {
+            data.extend(line.chars());
+            Ok(())
+        })
+    }
+}
 
-        if data.len() != width * height {
-            // eprintln!( "Error building grid: len {} vs width {} height {}", data.len(), width, height );
-            return Err(());
-        }
+/// Cells of a grid written as a single character in the input, usually
+/// implemented with `#[derive(GridCell)]` from `aoc_macros`:
+///
+/// ```rust,ignore
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+/// enum Tile {
+///     #[cell('#')]
+///     Wall,
+///     #[cell('.')]
+///     Floor,
+/// }
+///
+/// let grid: Grid<Tile> = input.parse()?;
+/// ```
+pub trait GridCell: Copy + TryFrom<char, Error = InvalidCell> + Into<char> {}
 
-        Ok(Self {
-            width,
-            height,
-            data,
-        })
+/// A character of the input that is not one of the cells of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCell(pub char);
+
+impl std::fmt::Display for InvalidCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid cell {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCell {}
+
+/// Derives a string into a grid of cells, one character each
+impl<T: GridCell> FromStr for Grid<T> {
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, T::try_from)
     }
 }
 
 /// Derives a string into a u8 / byte
 /// grid
 impl FromStr for Grid<u8> {
-    type Err = ();
+    type Err = GridParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let height = s.lines().count();
-        let width = s.lines().nth(0).ok_or(())?.len();
-        let data: Vec<u8> = s.lines().flat_map(|l| l.bytes()).collect();
-        Ok(Self {
-            width,
-            height,
-            data,
+        Self::parse_rows(s, |_, _, line, data| {
+            data.extend(line.bytes());
+            Ok(())
         })
     }
 }
@@ -354,6 +995,152 @@ mod tests {
         assert_eq!(8, grid.neighbors((1, 1)).count());
     }
 
+    #[test]
+    fn test_grid_neighbors() {
+        let mut grid: Grid<char> = Grid::from_str("abc\ndef\nghi").unwrap();
+        assert_eq!(
+            grid.neighbors((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(grid.neighbors((1, 1)).count(), 8);
+        assert_eq!(grid.orthogonal_neighbors((2, 1)).count(), 3);
+        assert_eq!(grid.neighbors((3, 0)).count(), 0);
+        assert_eq!(grid.neighbors((-1, 0)).count(), 0);
+        assert_eq!(grid.orthogonal_neighbors(Point::new(1, 2)).count(), 3);
+
+        let cells: Vec<_> = grid.orthogonal_cells((1, 0)).collect();
+        assert_eq!(
+            cells,
+            [
+                (Direction::Left, Point::new(0, 0), &'a'),
+                (Direction::Right, Point::new(2, 0), &'c'),
+                (Direction::Down, Point::new(1, 1), &'e'),
+            ]
+        );
+        let (delta, point, cell) = grid.neighbor_cells((2, 2)).next().unwrap();
+        assert_eq!(
+            (delta, point, *cell),
+            (Point::new(-1, -1), Point::new(1, 1), 'e')
+        );
+
+        grid.orthogonal_cells_mut((1, 1))
+            .for_each(|(_, _, cell)| *cell = '#');
+        grid.neighbor_cells_mut((0, 2))
+            .for_each(|(_, _, cell)| *cell = cell.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "a#c\n#E#\ng#i\n");
+    }
+
+    #[test]
+    fn test_grid_get() {
+        let mut grid: Grid<char> = Grid::from_str("ab\ncd").unwrap();
+        assert_eq!(grid.get((1_usize, 1_usize)), Some(&'d'));
+        assert_eq!(grid.get((2_usize, 0_usize)), None);
+        assert_eq!(grid.get((-1_isize, 1_isize)), None);
+        assert_eq!(grid.get((0_i32, -1_i32)), None);
+        assert_eq!(grid.get(Point::new(0, 1)), Some(&'c'));
+        *grid.get_mut(Point::new(1, 0)).unwrap() = 'x';
+        assert_eq!(grid[(1_isize, 0_isize)], 'x');
+        assert!(grid.get_mut(Point::new(-1, 0)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "(-1, 0) is out of the grid, which is 2 wide and 2 high")]
+    fn test_grid_index_out_of_bounds() {
+        let grid: Grid<char> = Grid::from_str("ab\ncd").unwrap();
+        let _ = grid[(-1_isize, 0_isize)];
+    }
+
+    #[test]
+    fn test_grid_lines() {
+        let mut grid: Grid<char> = Grid::from_str("abc\ndef").unwrap();
+        let lines = |lines: Vec<Vec<&char>>| -> Vec<String> {
+            lines.into_iter().map(|l| l.into_iter().collect()).collect()
+        };
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        assert_eq!(
+            lines(grid.columns().map(|c| c.collect()).collect()),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            lines(grid.diagonals().map(|d| d.collect()).collect()),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            lines(grid.anti_diagonals().map(|d| d.collect()).collect()),
+            ["a", "bd", "ce", "f"]
+        );
+
+        let ray: String = grid.ray((2, 1), Direction::Left).map(|(_, c)| c).collect();
+        assert_eq!(ray, "fed");
+        let (point, _) = grid.ray((0, 1), Point::new(1, -1)).last().unwrap();
+        assert_eq!(point, Point::new(1, 0));
+        assert_eq!(grid.ray((3, 0), Direction::Down).count(), 0);
+
+        grid.ray_mut((2, 1), Point::new(-1, -1))
+            .for_each(|(_, c)| *c = c.to_ascii_uppercase());
+        grid.ray_mut((0, 0), Direction::Right)
+            .skip(2)
+            .for_each(|(_, c)| *c = '#');
+        grid.column_mut(0).for_each(|c| *c = '|');
+        grid.rows_mut().last().unwrap()[2] = 'x';
+        assert_eq!(grid.to_string(), "|B#\n|ex\n");
+
+        let mut column: Grid<char> = Grid::from_str("a\nb\nc").unwrap();
+        let ray: String = column
+            .ray((0, 2), Point::new(1, -1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "c");
+        let ray: String = column
+            .ray_mut((0, 2), Point::new(1, -1))
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(ray, "c");
+        assert_eq!(column.ray_mut((0, 0), Point::new(-1, 1)).count(), 1);
+
+        let mut grid: Grid<char> = Grid::from_str("abc\ndef").unwrap();
+        for (i, column) in grid.columns_mut().enumerate() {
+            column.skip(i % 2).for_each(|c| *c = '.');
+        }
+        assert_eq!(grid.to_string(), ".b.\n...\n");
+        let mut grid: Grid<usize> = Grid::new(3, 2, 0);
+        grid.diagonals_mut()
+            .enumerate()
+            .for_each(|(k, d)| d.for_each(|v| *v = k));
+        assert_eq!(grid.to_string(), "123\n012\n");
+        let mut numbers = 0..;
+        grid.anti_diagonals_mut()
+            .for_each(|d| d.for_each(|v| *v = numbers.next().unwrap()));
+        assert_eq!(grid.to_string(), "013\n245\n");
+    }
+
+    #[test]
+    fn test_grid_transforms() {
+        let grid: Grid<char> = Grid::from_str("abc\ndef").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+
+        let square: Grid<char> = Grid::from_str("abc\ndef\nghi").unwrap();
+        for transform in [
+            (
+                Grid::transpose as fn(&Grid<char>) -> Grid<char>,
+                Grid::transpose_in_place as fn(&mut Grid<char>),
+            ),
+            (Grid::rotate_cw, Grid::rotate_cw_in_place),
+            (Grid::rotate_ccw, Grid::rotate_ccw_in_place),
+            (Grid::flip_horizontal, Grid::flip_horizontal_in_place),
+            (Grid::flip_vertical, Grid::flip_vertical_in_place),
+        ] {
+            let mut in_place = square.clone();
+            transform.1(&mut in_place);
+            assert_eq!(in_place.to_string(), transform.0(&square).to_string());
+        }
+    }
+
     #[test]
     fn test_grid_find_all() {
         let input = "..x\n.x.\n..x";
@@ -369,4 +1156,120 @@ mod tests {
         let mut iter = grid.find_all('@');
         assert_eq!(iter.next(), None);
     }
+
+    /// What `#[derive(GridCell)]` generates
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Floor,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = InvalidCell;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Floor),
+                c => Err(InvalidCell(c)),
+            }
+        }
+    }
+
+    impl From<Tile> for char {
+        fn from(tile: Tile) -> char {
+            match tile {
+                Tile::Wall => '#',
+                Tile::Floor => '.',
+            }
+        }
+    }
+
+    impl std::fmt::Display for Tile {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", char::from(*self))
+        }
+    }
+
+    impl GridCell for Tile {}
+
+    #[test]
+    fn test_grid_cells() {
+        let input = "###\n#..\n";
+
+        let grid: Grid<Tile> = Grid::from_str(input).unwrap();
+        assert_eq!(grid[(1, 1)], Tile::Floor);
+        assert_eq!(grid.find(Tile::Floor), Some((1, 1)));
+        assert_eq!(grid.to_string(), input);
+
+        assert_eq!(
+            Grid::<Tile>::from_str("##\n#@").err(),
+            Some(GridParseError::InvalidCell {
+                line: 2,
+                column: 2,
+                error: InvalidCell('@')
+            })
+        );
+        assert_eq!(
+            Grid::<Tile>::from_str("\n\n@#").unwrap_err().to_string(),
+            "invalid cell '@' at line 3, column 1"
+        );
+    }
+
+    #[test]
+    fn test_grid_parse_errors() {
+        // Blank lines around the grid and \r\n are fine
+        let grid: Grid<char> = Grid::from_str("\n#.\r\n.#\r\n\n").unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        let grid: Grid<u8> = Grid::from_str("#.\r\n.#\n\n").unwrap();
+        assert_eq!((grid.width, grid.height, grid.data[1]), (2, 2, b'.'));
+
+        assert_eq!(
+            Grid::<char>::from_str("\n \n").err(),
+            Some(GridParseError::Empty)
+        );
+        let ragged = Some(GridParseError::Ragged {
+            line: 3,
+            expected: 3,
+            found: 2,
+        });
+        assert_eq!(Grid::<char>::from_str("###\n#.#\n##\n").err(), ragged);
+        assert_eq!(Grid::<u8>::from_str("###\n#.#\n##\n").err(), ragged);
+        assert_eq!(Grid::<u8>::from_str("\n###\n##\n").err(), ragged);
+        assert!(Grid::<char>::from_str("##\n\n##").is_err());
+    }
+
+    #[test]
+    fn test_grid_parse_with() {
+        let grid =
+            Grid::parse_with("012\n345\n", |c| c.to_digit(10).ok_or(InvalidCell(c))).unwrap();
+        assert_eq!(grid[(2, 1)], 5_u32);
+        assert_eq!(
+            Grid::parse_with("01\n2x", |c| c.to_digit(10).ok_or(InvalidCell(c))).err(),
+            Some(GridParseError::InvalidCell {
+                line: 2,
+                column: 2,
+                error: InvalidCell('x')
+            })
+        );
+
+        let (grid, positions) =
+            Grid::parse_with_positions("#S.\n.E#\n", &['S', 'E'], Tile::Floor, Tile::try_from)
+                .unwrap();
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+        assert_eq!(positions[&'S'], (1, 0));
+        assert_eq!(positions[&'E'], (1, 1));
+        assert_eq!(
+            Grid::parse_with_positions("S.S", &['S'], Tile::Floor, Tile::try_from).err(),
+            Some(GridParseError::DuplicateMarker {
+                marker: 'S',
+                line: 1,
+                column: 3
+            })
+        );
+        assert_eq!(
+            Grid::parse_with_positions("S..", &['S', 'E'], Tile::Floor, Tile::try_from).err(),
+            Some(GridParseError::MissingMarker { marker: 'E' })
+        );
+    }
 }
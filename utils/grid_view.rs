@@ -0,0 +1,649 @@
+use std::ops::{Index, IndexMut};
+
+use super::direction::Direction;
+use super::grid::{self, Around, Coord, Grid, ALL_AROUND, ORTHOGONAL};
+use super::point::Point;
+
+/// A rectangle borrowed from a grid, indexed and iterated like a grid whose
+/// top left corner is the corner of the rectangle
+///
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Same as `GridView`, with mutable values
+///
+#[derive(Debug)]
+pub struct GridViewMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Grid<T> {
+    /// View of the rectangle of the grid starting at `from`, going right and
+    /// down. Panics if the rectangle does not fit in the grid
+    #[track_caller]
+    pub fn view(&self, from: impl Coord, width: usize, height: usize) -> GridView<'_, T> {
+        let (x, y) = self.view_origin(from, width, height);
+        GridView {
+            grid: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Same as `view`, with mutable values
+    #[track_caller]
+    pub fn view_mut(
+        &mut self,
+        from: impl Coord,
+        width: usize,
+        height: usize,
+    ) -> GridViewMut<'_, T> {
+        let (x, y) = self.view_origin(from, width, height);
+        GridViewMut {
+            grid: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[track_caller]
+    fn view_origin(&self, from: impl Coord, width: usize, height: usize) -> (usize, usize) {
+        match from.xy() {
+            Some((x, y)) if x + width <= self.width && y + height <= self.height => (x, y),
+            _ => panic!(
+                "a {}x{} view from {:?} does not fit in the grid, which is {} wide and {} high",
+                width, height, from, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Index in the data of the grid of a cell of a view, if it is within the
+/// view
+#[inline]
+fn index_of<T>(
+    grid: &Grid<T>,
+    (x, y): (usize, usize),
+    (width, height): (usize, usize),
+    coord: impl Coord,
+) -> Option<usize> {
+    let (cx, cy) = coord.xy()?;
+    (cx < width && cy < height).then_some((y + cy) * grid.width + x + cx)
+}
+
+#[track_caller]
+fn index_or_panic<T>(
+    grid: &Grid<T>,
+    origin: (usize, usize),
+    size: (usize, usize),
+    coord: impl Coord,
+) -> usize {
+    match index_of(grid, origin, size, coord) {
+        Some(i) => i,
+        None => panic!(
+            "{:?} is out of the view, which is {} wide and {} high",
+            coord, size.0, size.1
+        ),
+    }
+}
+
+/// Position relative to the view of a cell of the grid, if it is within the
+/// view
+#[inline]
+fn relative(origin: Point, (width, height): (usize, usize), point: Point) -> Option<Point> {
+    let point = point - origin;
+    point
+        .xy()
+        .is_some_and(|(x, y)| x < width && y < height)
+        .then_some(point)
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Value of a cell, or `None` if it is out of the view
+    #[inline]
+    pub fn get(&self, coord: impl Coord) -> Option<&'a T> {
+        let grid = self.grid;
+        index_of(grid, (self.x, self.y), (self.width, self.height), coord).map(|i| &grid.data[i])
+    }
+
+    /// Rows of the view, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let (x, width) = (self.x, self.width);
+        self.grid
+            .rows()
+            .skip(self.y)
+            .take(self.height)
+            .map(move |row| &row[x..x + width])
+    }
+
+    /// Columns of the view, from left to right, each from top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + 'a> + 'a {
+        let view = *self;
+        (0..self.width).map(move |x| view.column(x))
+    }
+
+    /// Values of a column, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &'a T> + 'a {
+        self.rows().filter_map(move |row| row.get(x))
+    }
+
+    /// Diagonals of the view going down to the right, like `Grid::diagonals`
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + 'a> + 'a {
+        let view = *self;
+        (0..(view.width + view.height).saturating_sub(1)).map(move |k| {
+            let start = match k < view.height {
+                true => (0, view.height - 1 - k),
+                false => (k + 1 - view.height, 0),
+            };
+            view.ray(start, Point::new(1, 1)).map(|(_, value)| value)
+        })
+    }
+
+    /// Diagonals of the view going down to the left, like
+    /// `Grid::anti_diagonals`
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + 'a> + 'a {
+        let view = *self;
+        (0..(view.width + view.height).saturating_sub(1)).map(move |k| {
+            let start = match k < view.width {
+                true => (k, 0),
+                false => (view.width - 1, k + 1 - view.width),
+            };
+            view.ray(start, Point::new(-1, 1)).map(|(_, value)| value)
+        })
+    }
+
+    /// Cells from a position, included, stepping in a direction until the
+    /// edge of the view, like `Grid::ray`
+    pub fn ray(
+        &self,
+        from: impl Coord,
+        step: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let view = *self;
+        let step = step.into();
+        let length = grid::ray_length(view.width, view.height, from, step);
+        let from = from
+            .xy()
+            .map_or(Point::new(0, 0), |(x, y)| grid::position(x, y));
+        (0..length as i32).map(move |i| {
+            let point = Point::new(from.x + i * step.x, from.y + i * step.y);
+            (point, view.get(point).expect("rays stay in the view"))
+        })
+    }
+
+    /// Positions of the neighbors of a cell within the view, diagonals
+    /// included
+    pub fn neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ALL_AROUND
+            .into_iter()
+            .filter(move |&(dx, dy)| around.contains(dx, dy))
+            .map(move |(dx, dy)| grid::offset(point, dx, dy))
+    }
+
+    /// Positions of the neighbors of a cell within the view, without the
+    /// diagonals
+    pub fn orthogonal_neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ORTHOGONAL
+            .into_iter()
+            .filter(move |&(_, dx, dy)| around.contains(dx, dy))
+            .map(move |(_, dx, dy)| grid::offset(point, dx, dy))
+    }
+
+    /// Neighbors of a cell within the view, without the diagonals, like
+    /// `Grid::orthogonal_cells`
+    pub fn orthogonal_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Direction, Point, &'a T)> + 'a {
+        let view = *self;
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ORTHOGONAL
+            .into_iter()
+            .filter(move |&(_, dx, dy)| around.contains(dx, dy))
+            .map(move |(direction, dx, dy)| {
+                let (x, y) = grid::offset(point, dx, dy);
+                (
+                    direction,
+                    grid::position(x, y),
+                    view.get((x, y)).expect("neighbors are within the view"),
+                )
+            })
+    }
+
+    /// Neighbors of a cell within the view, diagonals included, like
+    /// `Grid::neighbor_cells`
+    pub fn neighbor_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Point, Point, &'a T)> + 'a {
+        let view = *self;
+        let around = Around::new(self.width, self.height, point);
+        let point = point.xy().unwrap_or_default();
+        ALL_AROUND
+            .into_iter()
+            .filter(move |&(dx, dy)| around.contains(dx, dy))
+            .map(move |(dx, dy)| {
+                let (x, y) = grid::offset(point, dx, dy);
+                let delta = Point::new(dx as i32, dy as i32);
+                (
+                    delta,
+                    grid::position(x, y),
+                    view.get((x, y)).expect("neighbors are within the view"),
+                )
+            })
+    }
+
+    /// A narrower view, `from` being relative to this view
+    #[track_caller]
+    pub fn view(&self, from: impl Coord, width: usize, height: usize) -> GridView<'a, T> {
+        let (x, y) = self.sub_view_origin(from, width, height);
+        self.grid.view((x, y), width, height)
+    }
+
+    #[track_caller]
+    fn sub_view_origin(&self, from: impl Coord, width: usize, height: usize) -> (usize, usize) {
+        match from.xy() {
+            Some((x, y)) if x + width <= self.width && y + height <= self.height => {
+                (self.x + x, self.y + y)
+            }
+            _ => panic!(
+                "a {}x{} view from {:?} does not fit in the view, which is {} wide and {} high",
+                width, height, from, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copies the view into a grid of its own
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.rows().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<'a, T: Copy + PartialEq> GridView<'a, T> {
+    /// Position of the first cell of the view equal to the value, row by row
+    pub fn find(&self, value: T) -> Option<(usize, usize)> {
+        self.find_all(value).next()
+    }
+
+    /// Positions of the cells of the view equal to the value, row by row
+    pub fn find_all(&self, value: T) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.rows().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, &v)| v == value)
+                .map(move |(x, _)| (x, y))
+        })
+    }
+}
+
+impl<'a, T> GridViewMut<'a, T> {
+    /// Read only view of the same rectangle
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self.grid,
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Value of a cell, or `None` if it is out of the view
+    #[inline]
+    pub fn get(&self, coord: impl Coord) -> Option<&T> {
+        let i = index_of(
+            self.grid,
+            (self.x, self.y),
+            (self.width, self.height),
+            coord,
+        )?;
+        Some(&self.grid.data[i])
+    }
+
+    /// Mutable value of a cell, or `None` if it is out of the view
+    #[inline]
+    pub fn get_mut(&mut self, coord: impl Coord) -> Option<&mut T> {
+        let i = index_of(
+            self.grid,
+            (self.x, self.y),
+            (self.width, self.height),
+            coord,
+        )?;
+        Some(&mut self.grid.data[i])
+    }
+
+    /// Rows of the view, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.as_view().rows()
+    }
+
+    /// Same as `rows`, with mutable values
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        let (x, width) = (self.x, self.width);
+        self.grid
+            .rows_mut()
+            .skip(self.y)
+            .take(self.height)
+            .map(move |row| &mut row[x..x + width])
+    }
+
+    /// Columns of the view, from left to right, each from top to bottom
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.as_view().columns()
+    }
+
+    /// Values of a column, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.as_view().column(x)
+    }
+
+    /// Same as `column`, with mutable values
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> + '_ {
+        self.rows_mut().filter_map(move |row| row.get_mut(x))
+    }
+
+    /// Diagonals of the view going down to the right, like `Grid::diagonals`
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.as_view().diagonals()
+    }
+
+    /// Diagonals of the view going down to the left, like
+    /// `Grid::anti_diagonals`
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.as_view().anti_diagonals()
+    }
+
+    /// Cells from a position, included, stepping in a direction until the
+    /// edge of the view, like `Grid::ray`
+    pub fn ray(
+        &self,
+        from: impl Coord,
+        step: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.as_view().ray(from, step)
+    }
+
+    /// Same as `ray`, with mutable values
+    pub fn ray_mut(
+        &mut self,
+        from: impl Coord,
+        step: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &mut T)> + '_ {
+        let step = step.into();
+        let length = grid::ray_length(self.width, self.height, from, step);
+        let origin = grid::position(self.x, self.y);
+        let from = from
+            .xy()
+            .map_or(origin, |(x, y)| origin + grid::position(x, y));
+        self.grid
+            .ray_mut(from, step)
+            .take(length)
+            .map(move |(point, value)| (point - origin, value))
+    }
+
+    /// Positions of the neighbors of a cell within the view, diagonals
+    /// included
+    pub fn neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        self.as_view().neighbors(point)
+    }
+
+    /// Positions of the neighbors of a cell within the view, without the
+    /// diagonals
+    pub fn orthogonal_neighbors(&self, point: impl Coord) -> impl Iterator<Item = (usize, usize)> {
+        self.as_view().orthogonal_neighbors(point)
+    }
+
+    /// Neighbors of a cell within the view, without the diagonals, like
+    /// `Grid::orthogonal_cells`
+    pub fn orthogonal_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Direction, Point, &T)> + '_ {
+        self.as_view().orthogonal_cells(point)
+    }
+
+    /// Same as `orthogonal_cells`, with mutable values
+    pub fn orthogonal_cells_mut(
+        &mut self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Direction, Point, &mut T)> + '_ {
+        let (origin, size) = (grid::position(self.x, self.y), (self.width, self.height));
+        let point = self.grid_point(point);
+        self.grid
+            .orthogonal_cells_mut(point)
+            .filter_map(move |(direction, point, value)| {
+                relative(origin, size, point).map(|point| (direction, point, value))
+            })
+    }
+
+    /// Neighbors of a cell within the view, diagonals included, like
+    /// `Grid::neighbor_cells`
+    pub fn neighbor_cells(
+        &self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Point, Point, &T)> + '_ {
+        self.as_view().neighbor_cells(point)
+    }
+
+    /// Same as `neighbor_cells`, with mutable values
+    pub fn neighbor_cells_mut(
+        &mut self,
+        point: impl Coord,
+    ) -> impl Iterator<Item = (Point, Point, &mut T)> + '_ {
+        let (origin, size) = (grid::position(self.x, self.y), (self.width, self.height));
+        let point = self.grid_point(point);
+        self.grid
+            .neighbor_cells_mut(point)
+            .filter_map(move |(delta, point, value)| {
+                relative(origin, size, point).map(|point| (delta, point, value))
+            })
+    }
+
+    /// Position in the grid of a cell of the view. Cells out of the view are
+    /// moved out of the grid, so that they have no neighbors
+    fn grid_point(&self, point: impl Coord) -> (usize, usize) {
+        match point.xy() {
+            Some((x, y)) if x < self.width && y < self.height => (self.x + x, self.y + y),
+            _ => (usize::MAX, usize::MAX),
+        }
+    }
+
+    /// Sets all the cells of the view to the same value
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.rows_mut().for_each(|row| row.fill(value.clone()));
+    }
+}
+
+impl<T: Copy + PartialEq> GridViewMut<'_, T> {
+    /// Position of the first cell of the view equal to the value, row by row
+    pub fn find(&self, value: T) -> Option<(usize, usize)> {
+        self.as_view().find(value)
+    }
+
+    /// Positions of the cells of the view equal to the value, row by row
+    pub fn find_all(&self, value: T) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.as_view().find_all(value)
+    }
+}
+
+impl<T: Clone> GridViewMut<'_, T> {
+    /// Copies the view into a grid of its own
+    pub fn to_grid(&self) -> Grid<T> {
+        self.as_view().to_grid()
+    }
+}
+
+///
+/// Using any coordinates relative to the view to lookup a value. Panics out
+/// of the view, see `get` otherwise
+///
+impl<T, C: Coord> Index<C> for GridView<'_, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, coord: C) -> &Self::Output {
+        let size = (self.width, self.height);
+        &self.grid.data[index_or_panic(self.grid, (self.x, self.y), size, coord)]
+    }
+}
+
+impl<T, C: Coord> Index<C> for GridViewMut<'_, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, coord: C) -> &Self::Output {
+        let size = (self.width, self.height);
+        &self.grid.data[index_or_panic(self.grid, (self.x, self.y), size, coord)]
+    }
+}
+
+///
+/// Using any coordinates relative to the view to set a value
+///
+impl<T, C: Coord> IndexMut<C> for GridViewMut<'_, T> {
+    #[track_caller]
+    fn index_mut(&mut self, coord: C) -> &mut Self::Output {
+        let size = (self.width, self.height);
+        let i = index_or_panic(self.grid, (self.x, self.y), size, coord);
+        &mut self.grid.data[i]
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for GridViewMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_grid_view() {
+        let mut grid: Grid<char> = Grid::from_str("abcd\nefgh\nijkl").unwrap();
+        let view = grid.view((1, 1), 2, 2);
+        assert_eq!(view.to_string(), "fg\njk\n");
+        assert_eq!(view[(1, 0)], 'g');
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.column(1).collect::<String>(), "gk");
+        assert_eq!(view.view((1, 0), 1, 2).to_string(), "g\nk\n");
+        assert_eq!(view.to_grid().rotate_cw().to_string(), "jf\nkg\n");
+
+        let lines = |lines: Vec<String>| lines.join(" ");
+        assert_eq!(
+            lines(view.diagonals().map(|d| d.collect()).collect()),
+            "j fk g"
+        );
+        assert_eq!(
+            lines(view.anti_diagonals().map(|d| d.collect()).collect()),
+            "f gj k"
+        );
+        let ray: String = view
+            .ray((1, 1), Point::new(-1, -1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "kf");
+        assert_eq!(view.ray((0, 1), Direction::Up).count(), 2);
+        assert_eq!(
+            view.neighbors((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(view.orthogonal_neighbors((1, 1)).count(), 2);
+        let around: String = view.orthogonal_cells((0, 0)).map(|(_, _, c)| c).collect();
+        assert_eq!(around, "gj");
+        assert_eq!(view.neighbor_cells((1, 0)).count(), 3);
+        assert_eq!((view.find('k'), view.find('a')), (Some((1, 1)), None));
+        assert_eq!(view.find_all('g').collect::<Vec<_>>(), [(1, 0)]);
+
+        let mut view = grid.view_mut((2, 0), 2, 3);
+        view[(0, 2)] = 'x';
+        view.column_mut(1).for_each(|c| *c = '.');
+        assert_eq!(grid.to_string(), "abc.\nefg.\nijx.\n");
+
+        let mut view = grid.view_mut((1, 0), 2, 2);
+        assert_eq!(view.find('g'), Some((1, 1)));
+        assert_eq!(view.neighbors(Point::new(0, 0)).count(), 3);
+        assert_eq!(view.orthogonal_neighbors((-1, 0)).count(), 0);
+        let diagonals: Vec<String> = view.diagonals().map(|d| d.collect()).collect();
+        assert_eq!(diagonals, ["f", "bg", "c"]);
+        let ray: Vec<_> = view
+            .ray_mut((1, 1), Direction::Up)
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(ray, [Point::new(1, 1), Point::new(1, 0)]);
+        view.ray_mut((0, 1), Direction::Right)
+            .for_each(|(_, c)| *c = c.to_ascii_uppercase());
+        view.orthogonal_cells_mut((1, 0))
+            .for_each(|(_, _, c)| *c = '+');
+        let around: Vec<_> = view
+            .neighbor_cells_mut((0, 0))
+            .map(|(d, p, _)| (d, p))
+            .collect();
+        assert_eq!(
+            around,
+            [
+                (Point::new(1, 0), Point::new(1, 0)),
+                (Point::new(0, 1), Point::new(0, 1)),
+                (Point::new(1, 1), Point::new(1, 1))
+            ]
+        );
+        assert_eq!(grid.to_string(), "a+c.\neF+.\nijx.\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in the grid")]
+    fn test_grid_view_out_of_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        grid.view((2, 2), 2, 1);
+    }
+}
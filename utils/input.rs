@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+
+use super::config::Config;
+use super::encryption::{EncryptionError, Key, ENCRYPTED_EXTENSION};
+
+/// Name of the file storing the expected answers, in the input folder
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// Errors that can happen when fetching puzzle inputs and answers
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file is not there
+    Missing(PathBuf),
+    /// The file exists but could not be read or written
+    Io(PathBuf, std::io::Error),
+    /// The answers file is not valid TOML
+    Answers(PathBuf, toml::de::Error),
+    /// No session cookie configured, so we cannot download
+    NoSession,
+    /// No key configured, so we cannot encrypt or decrypt
+    NoKey,
+    /// An encrypted input could not be decrypted
+    Decrypt(PathBuf, EncryptionError),
+    /// The request to adventofcode.com failed
+    Download(String),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(f, "input missing: {}", path.display()),
+            InputError::Io(path, e) => write!(f, "cannot access {}: {}", path.display(), e),
+            InputError::Answers(path, e) => write!(f, "invalid answers {}: {}", path.display(), e),
+            InputError::NoSession => write!(f, "no session cookie configured"),
+            InputError::NoKey => write!(f, "no encryption key configured"),
+            InputError::Decrypt(path, e) => write!(f, "{}: {}", path.display(), e),
+            InputError::Download(e) => write!(f, "download failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// An input embedded in the binary with `include_input!` from `aoc_macros`.
+/// Missing files do not stop the build, the day then fails at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddedInput {
+    Present(&'static str),
+    /// Path of the file that was missing at compile time
+    Missing(&'static str),
+}
+
+impl EmbeddedInput {
+    pub fn get(&self) -> Result<&'static str, InputError> {
+        match self {
+            EmbeddedInput::Present(input) => Ok(input),
+            EmbeddedInput::Missing(path) => Err(InputError::Missing(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Name of the input file of a day, e.g. `day05.txt`
+pub fn input_file_name(day: usize) -> String {
+    format!("day{:02}.txt", day)
+}
+
+/// Folder holding the named inputs of a day, e.g. `day05/`
+pub fn named_inputs_dir_name(day: usize) -> String {
+    format!("day{:02}", day)
+}
+
+/// Reads the input of a day from the configured input folder.
+///
+/// If the plain text file is not there, we look for an encrypted one
+/// (`day05.txt.enc`) and decrypt it with the configured key.
+pub fn load_input(config: &Config, year: u16, day: usize) -> Result<String, InputError> {
+    let path = config.input_dir(year).join(input_file_name(day));
+    read_input(config, &path)
+}
+
+/// Lists the names of the extra inputs of a day, sorted.
+///
+/// Besides the main `day05.txt`, a day can have inputs from other accounts in
+/// a `day05/` folder, e.g. `day05/alice.txt` or `day05/bob.txt.enc`.
+pub fn named_inputs(config: &Config, year: u16, day: usize) -> Result<Vec<String>, InputError> {
+    let dir = config.input_dir(year).join(named_inputs_dir_name(day));
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(&dir).map_err(|e| InputError::Io(dir.clone(), e))?;
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let name = name.strip_suffix(".enc").unwrap_or(&name);
+            name.strip_suffix(".txt").map(|n| n.to_string())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Reads one of the extra inputs of a day, see [`named_inputs`]
+pub fn load_named_input(
+    config: &Config,
+    year: u16,
+    day: usize,
+    name: &str,
+) -> Result<String, InputError> {
+    let path = config
+        .input_dir(year)
+        .join(named_inputs_dir_name(day))
+        .join(format!("{}.txt", name));
+    read_input(config, &path)
+}
+
+/// Encrypts all the plain text inputs of a year, writing `dayNN.txt.enc` files
+/// next to them. Inputs whose `.enc` file already decrypts to the same text
+/// are left alone, so that the encrypted files only change with the inputs.
+/// Returns the written files
+pub fn encrypt_inputs(config: &Config, year: u16) -> Result<Vec<PathBuf>, InputError> {
+    let key = config.key.as_ref().ok_or(InputError::NoKey)?;
+
+    let mut written = Vec::new();
+    for path in list_inputs(&config.input_dir(year), ".txt")? {
+        let input = std::fs::read(&path).map_err(|e| InputError::Io(path.clone(), e))?;
+        let encrypted_path = encrypted_path(&path);
+        if decrypt_file(key, &encrypted_path).is_ok_and(|existing| existing.as_bytes() == input) {
+            continue;
+        }
+        std::fs::write(&encrypted_path, key.encrypt(&input))
+            .map_err(|e| InputError::Io(encrypted_path.clone(), e))?;
+        written.push(encrypted_path);
+    }
+    Ok(written)
+}
+
+/// Decrypts all the encrypted inputs of a year, writing the plain text files
+/// next to them. Returns the written files
+pub fn decrypt_inputs(config: &Config, year: u16) -> Result<Vec<PathBuf>, InputError> {
+    let key = config.key.as_ref().ok_or(InputError::NoKey)?;
+
+    let mut written = Vec::new();
+    for encrypted_path in list_inputs(&config.input_dir(year), ".txt.enc")? {
+        let input = decrypt_file(key, &encrypted_path)?;
+        let path = encrypted_path.with_extension("");
+        std::fs::write(&path, input).map_err(|e| InputError::Io(path.clone(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Reads the expected answers for a day, as `(part 1, part 2)`.
+///
+/// They are stored in `answers.toml` in the input folder, with a table per
+/// input, named like the input file:
+///
+/// ```toml
+/// [day05]
+/// part1 = 143
+/// part2 = "some text"
+/// ```
+pub fn load_answers(
+    config: &Config,
+    year: u16,
+    day: usize,
+) -> Result<(Option<String>, Option<String>), InputError> {
+    let dir = config.input_dir(year);
+    read_answers(&dir, &named_inputs_dir_name(day))
+}
+
+/// Reads the expected answers for one of the extra inputs of a day. They are
+/// stored in `answers.toml` in the `day05/` folder, under the input name.
+pub fn load_named_answers(
+    config: &Config,
+    year: u16,
+    day: usize,
+    name: &str,
+) -> Result<(Option<String>, Option<String>), InputError> {
+    let dir = config.input_dir(year).join(named_inputs_dir_name(day));
+    read_answers(&dir, name)
+}
+
+/// Downloads the input of a day from adventofcode.com into the input folder.
+/// Returns the path of the written file
+pub fn download_input(config: &Config, year: u16, day: usize) -> Result<PathBuf, InputError> {
+    let session = config.session.as_ref().ok_or(InputError::NoSession)?;
+    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+
+    let input = ureq::get(&url)
+        .header("Cookie", &format!("session={}", session))
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| InputError::Download(e.to_string()))?;
+
+    let dir = config.input_dir(year);
+    std::fs::create_dir_all(&dir).map_err(|e| InputError::Io(dir.clone(), e))?;
+    let path = dir.join(input_file_name(day));
+    std::fs::write(&path, input).map_err(|e| InputError::Io(path.clone(), e))?;
+    Ok(path)
+}
+
+/// Reads an input file, or its encrypted version if the plain text one is missing
+fn read_input(config: &Config, path: &Path) -> Result<String, InputError> {
+    match read(path) {
+        Err(InputError::Missing(_)) => {}
+        result => return result,
+    }
+
+    let encrypted_path = encrypted_path(path);
+    if !encrypted_path.exists() {
+        return Err(InputError::Missing(path.to_path_buf()));
+    }
+    let key = config.key.as_ref().ok_or(InputError::NoKey)?;
+    decrypt_file(key, &encrypted_path)
+}
+
+/// Reads the answers of the input `name` from the `answers.toml` of a folder
+fn read_answers(dir: &Path, name: &str) -> Result<(Option<String>, Option<String>), InputError> {
+    let path = dir.join(ANSWERS_FILE_NAME);
+    let answers: toml::Table = match read(&path) {
+        Ok(content) => toml::from_str(&content).map_err(|e| InputError::Answers(path, e))?,
+        Err(InputError::Missing(_)) => return Ok((None, None)),
+        Err(e) => return Err(e),
+    };
+
+    let Some(toml::Value::Table(input)) = answers.get(name) else {
+        return Ok((None, None));
+    };
+    let part = |key: &str| {
+        input.get(key).map(|v| match v {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    };
+    Ok((part("part1"), part("part2")))
+}
+
+/// Path of the encrypted version of an input, e.g. `day05.txt.enc`
+fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    path.with_file_name(name)
+}
+
+fn decrypt_file(key: &Key, path: &Path) -> Result<String, InputError> {
+    let data = std::fs::read(path).map_err(|e| InputError::Io(path.to_path_buf(), e))?;
+    let input = key
+        .decrypt(&data)
+        .map_err(|e| InputError::Decrypt(path.to_path_buf(), e))?;
+    String::from_utf8(input)
+        .map_err(|_| InputError::Decrypt(path.to_path_buf(), EncryptionError::InvalidData))
+}
+
+/// Lists the input files with the given suffix, sorted by name: the
+/// `dayNN` files of the input folder and the named inputs in the `dayNN/`
+/// folders
+fn list_inputs(dir: &Path, suffix: &str) -> Result<Vec<PathBuf>, InputError> {
+    let is_day = |name: &str| {
+        name.strip_prefix("day")
+            .is_some_and(|n| n.len() == 2 && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+    let read_dir = |dir: &Path| {
+        std::fs::read_dir(dir)
+            .map(|entries| entries.filter_map(|entry| entry.ok()).map(|e| e.path()))
+            .map_err(|e| InputError::Io(dir.to_path_buf(), e))
+    };
+
+    let mut inputs = Vec::new();
+    for path in read_dir(dir)? {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if path.is_dir() && is_day(name) {
+            inputs.extend(read_dir(&path)?.filter(|p| has_suffix(p, suffix)));
+        } else if name.strip_suffix(suffix).is_some_and(is_day) {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn has_suffix(path: &Path, suffix: &str) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(suffix))
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => InputError::Missing(path.to_path_buf()),
+        _ => InputError::Io(path.to_path_buf(), e),
+    })
+}
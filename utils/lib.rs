@@ -1,13 +1,29 @@
+pub mod bit_grid;
+pub mod config;
 pub mod directed_point;
 pub mod direction;
+pub mod encryption;
 pub mod grid;
+pub mod grid_view;
+pub mod input;
+pub mod logging;
+pub mod memoize;
 pub mod moves;
+pub mod parse;
 pub mod point;
+pub mod region;
+pub mod registry;
+pub mod runner;
+pub mod search;
+pub mod solution;
+pub mod sparse_grid;
+pub mod torus;
 
 // Export the types directly
+pub use config::Config;
 pub use directed_point::DirectedPoint;
 pub use direction::Direction;
 pub use grid::Grid;
 pub use moves::Moves;
 pub use point::Point;
-
+pub use runner::Runner;
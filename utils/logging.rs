@@ -0,0 +1,34 @@
+use std::io::IsTerminal;
+
+use tracing_subscriber::EnvFilter;
+
+use super::config::Config;
+
+// Days log through these, so the year crates do not need their own dependency
+pub use tracing::{debug, debug_span, error, info, info_span, trace, warn};
+
+/// Installs the logger, printing to stderr.
+///
+/// The base level comes from the number of `-v` flags: warnings only by
+/// default, `-v` for debug and `-vv` for trace. The `log` configuration entry
+/// (or `AOC_LOG`) can refine it per target, each day logging under its module
+/// path, e.g. `log = "aoc2024::days::day16=trace"`.
+pub fn init(config: &Config) {
+    let level = match config.verbosity {
+        0 => "warn",
+        1 => "debug",
+        _ => "trace",
+    };
+    let directives = match &config.log {
+        Some(log) => format!("{},{}", level, log),
+        None => level.to_string(),
+    };
+
+    // Fails only if a logger is already installed, which is fine
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::builder().parse_lossy(directives))
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time()
+        .try_init();
+}
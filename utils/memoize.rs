@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Storage for the results of a memoized function, see the `#[memoize]`
+/// attribute of `aoc_macros`
+pub trait Cache<K, V>: Clear {
+    fn get(&mut self, key: &K) -> Option<V>;
+    fn insert(&mut self, key: K, value: V);
+}
+
+/// Forgets all the values of a cache
+pub trait Clear {
+    fn clear(&mut self);
+}
+
+/// Unbounded cache, the default one
+pub struct HashCache<K, V>(Option<HashMap<K, V>>);
+
+impl<K, V> HashCache<K, V> {
+    pub const fn new() -> Self {
+        Self(None)
+    }
+}
+
+impl<K, V> Default for HashCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Cache<K, V> for HashCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.0.as_ref()?.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.0.get_or_insert_with(HashMap::new).insert(key, value);
+    }
+}
+
+impl<K, V> Clear for HashCache<K, V> {
+    fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+/// Cache for functions of a small integer, stored in an array of `N` values.
+/// Keys outside of the array are not cached
+pub struct ArrayCache<V, const N: usize>([Option<V>; N]);
+
+impl<V, const N: usize> ArrayCache<V, N> {
+    pub const fn new() -> Self {
+        Self([const { None }; N])
+    }
+}
+
+impl<V, const N: usize> Default for ArrayCache<V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone, const N: usize> Cache<usize, V> for ArrayCache<V, N> {
+    fn get(&mut self, key: &usize) -> Option<V> {
+        self.0.get(*key)?.clone()
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        if let Some(slot) = self.0.get_mut(key) {
+            *slot = Some(value);
+        }
+    }
+}
+
+impl<V, const N: usize> Clear for ArrayCache<V, N> {
+    fn clear(&mut self) {
+        self.0.iter_mut().for_each(|slot| *slot = None);
+    }
+}
+
+/// Cache keeping the `CAPACITY` most recently used values
+pub struct LruCache<K, V, const CAPACITY: usize>(Option<lru::LruCache<K, V>>);
+
+impl<K, V, const CAPACITY: usize> LruCache<K, V, CAPACITY> {
+    pub const fn new() -> Self {
+        Self(None)
+    }
+}
+
+impl<K, V, const CAPACITY: usize> Default for LruCache<K, V, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone, const CAPACITY: usize> Cache<K, V> for LruCache<K, V, CAPACITY> {
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.0.as_mut()?.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let capacity = NonZeroUsize::new(CAPACITY).expect("LRU caches hold at least one value");
+        self.0
+            .get_or_insert_with(|| lru::LruCache::new(capacity))
+            .put(key, value);
+    }
+}
+
+impl<K, V, const CAPACITY: usize> Clear for LruCache<K, V, CAPACITY> {
+    fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+/// Hits and misses of a cache since it was last cleared. Only counted in
+/// debug builds, to keep release builds fast
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl std::fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)
+    }
+}
+
+/// The cache of a memoized function, shared by all its calls
+pub struct Memo<C> {
+    cache: Mutex<C>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<C: Clear> Memo<C> {
+    pub const fn new(cache: C) -> Self {
+        Self {
+            cache: Mutex::new(cache),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    // The lock is never held while computing a value, so a panicking
+    // function leaves the cache usable
+    fn lock(&self) -> MutexGuard<'_, C> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get<K, V>(&self, key: &K) -> Option<V>
+    where
+        C: Cache<K, V>,
+    {
+        let value = self.lock().get(key);
+        if cfg!(debug_assertions) {
+            let counter = match value {
+                Some(_) => &self.hits,
+                None => &self.misses,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    pub fn insert<K, V>(&self, key: K, value: V)
+    where
+        C: Cache<K, V>,
+    {
+        self.lock().insert(key, value);
+    }
+
+    /// Forgets the values and resets the statistics
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A memoized function, registered by `#[memoize]` so that all the caches
+/// can be cleared at once
+pub struct Memoized {
+    /// Path of the function, e.g. `aoc2024::days::day11::count`
+    pub name: &'static str,
+    pub clear: fn(),
+    pub stats: fn() -> CacheStats,
+}
+
+inventory::collect!(Memoized);
+
+/// Clears the caches of all the memoized functions, so that a run does not
+/// benefit from the values computed by the previous one
+pub fn clear_all() {
+    for memoized in inventory::iter::<Memoized> {
+        (memoized.clear)();
+    }
+}
+
+/// Statistics of the caches of all the memoized functions, sorted by name
+pub fn stats() -> Vec<(&'static str, CacheStats)> {
+    let mut stats: Vec<_> = inventory::iter::<Memoized>
+        .into_iter()
+        .map(|m| (m.name, (m.stats)()))
+        .collect();
+    stats.sort_by_key(|(name, _)| *name);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caches() {
+        let memo: Memo<HashCache<(u32, u32), u64>> = Memo::new(HashCache::new());
+        assert_eq!(memo.get(&(1, 2)), None);
+        memo.insert((1, 2), 3);
+        assert_eq!(memo.get(&(1, 2)), Some(3));
+        if cfg!(debug_assertions) {
+            assert_eq!(memo.stats(), CacheStats { hits: 1, misses: 1 });
+        }
+        memo.clear();
+        assert_eq!(memo.get(&(1, 2)), None);
+
+        let mut array: ArrayCache<u64, 4> = ArrayCache::new();
+        array.insert(3, 9);
+        array.insert(4, 16);
+        assert_eq!(array.get(&3), Some(9));
+        assert_eq!(array.get(&4), None);
+
+        let mut lru: LruCache<u32, u32, 2> = LruCache::new();
+        lru.insert(1, 1);
+        lru.insert(2, 2);
+        assert_eq!(lru.get(&1), Some(1));
+        lru.insert(3, 3);
+        assert_eq!(lru.get(&2), None);
+        assert_eq!(lru.get(&1), Some(1));
+    }
+}
@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+/// Errors when parsing a value from the input, usually with
+/// `#[derive(FromInput)]` from `aoc_macros`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text does not match the pattern
+    Mismatch { expected: String, found: String },
+    /// The text of a field could not be parsed into its type
+    Field {
+        field: &'static str,
+        text: String,
+        reason: String,
+    },
+    /// Something was left after the end of the pattern
+    Trailing(String),
+    /// The error happened on this line of the input, counting from 1
+    Line(usize, Box<ParseError>),
+}
+
+impl ParseError {
+    /// Places the error on a line of the input. Errors already on a line are
+    /// relative to the one given, e.g. the second line of a block
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            ParseError::Line(l, e) => ParseError::Line(line + l - 1, e),
+            e => ParseError::Line(line, Box::new(e)),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Mismatch { expected, found } => {
+                write!(f, "expected {:?}, found {:?}", expected, found)
+            }
+            ParseError::Field {
+                field,
+                text,
+                reason,
+            } => write!(f, "invalid {} {:?}: {}", field, text, reason),
+            ParseError::Trailing(text) => write!(f, "unexpected {:?} at the end", text),
+            ParseError::Line(line, e) => write!(f, "line {}: {}", line, e),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses each non-empty line of the input, errors tell the line number
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.at_line(i + 1)))
+        .collect()
+}
+
+/// Parses each block of the input, blocks being separated by empty lines.
+/// Errors tell the line number
+pub fn blocks<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut block = String::new();
+    for (i, line) in input.lines().chain(std::iter::once("")).enumerate() {
+        if !line.trim().is_empty() {
+            start.get_or_insert(i + 1);
+            block.push_str(line);
+            block.push('\n');
+        } else if let Some(line) = start.take() {
+            blocks.push(block.parse().map_err(|e: ParseError| e.at_line(line))?);
+            block.clear();
+        }
+    }
+    Ok(blocks)
+}
+
+/// Walks through a text following a pattern, used by the `FromStr`
+/// implementations generated by `#[derive(FromInput)]`.
+///
+/// Errors in text spanning several lines are placed on the line they happen,
+/// counting from the first one. Text with `\r\n` line endings should go
+/// through [`lines`] or [`blocks`].
+pub struct Scanner<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text: text.trim_end(),
+            position: 0,
+        }
+    }
+
+    /// Skips the expected literal text
+    pub fn literal(&mut self, expected: &str) -> Result<(), ParseError> {
+        let rest = &self.text[self.position..];
+        if !rest.starts_with(expected) {
+            let found = rest.chars().take(expected.chars().count()).collect();
+            return Err(self.error(ParseError::Mismatch {
+                expected: expected.to_string(),
+                found,
+            }));
+        }
+        self.position += expected.len();
+        Ok(())
+    }
+
+    /// Parses a field, from the current position up to the next occurrence
+    /// of `until` on the line, or to the end of the line if `until` is empty.
+    /// Spaces around the value are ignored
+    pub fn field<T>(&mut self, field: &'static str, until: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let rest = &self.text[self.position..];
+        let line = rest.split('\n').next().unwrap_or_default();
+        let end = match until {
+            "" => line.len(),
+            until => line.find(until).ok_or_else(|| {
+                self.error(ParseError::Mismatch {
+                    expected: until.to_string(),
+                    found: line.to_string(),
+                })
+            })?,
+        };
+        let text = line[..end].trim();
+        let value = text.parse().map_err(|e: T::Err| {
+            self.error(ParseError::Field {
+                field,
+                text: text.to_string(),
+                reason: e.to_string(),
+            })
+        })?;
+        self.position += end;
+        Ok(value)
+    }
+
+    /// Checks that nothing is left
+    pub fn finish(self) -> Result<(), ParseError> {
+        match self.text[self.position..].trim() {
+            "" => Ok(()),
+            rest => Err(self.error(ParseError::Trailing(rest.to_string()))),
+        }
+    }
+
+    fn error(&self, e: ParseError) -> ParseError {
+        match self.text.contains('\n') {
+            true => e.at_line(self.text[..self.position].matches('\n').count() + 1),
+            false => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What `#[derive(FromInput)]` generates for `"{x},{y}\nv={v}"`
+    #[derive(Debug, PartialEq)]
+    struct Block {
+        x: usize,
+        y: usize,
+        v: isize,
+    }
+
+    impl FromStr for Block {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut scanner = Scanner::new(s);
+            let x = scanner.field::<usize>("x", ",")?;
+            scanner.literal(",")?;
+            let y = scanner.field::<usize>("y", "")?;
+            scanner.literal("\nv=")?;
+            let v = scanner.field::<isize>("v", "")?;
+            scanner.finish()?;
+            Ok(Self { x, y, v })
+        }
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1,2\r\nv=-3\r\n\r\n4, 5\nv=6\n";
+        assert_eq!(
+            blocks::<Block>(input),
+            Ok(vec![
+                Block { x: 1, y: 2, v: -3 },
+                Block { x: 4, y: 5, v: 6 }
+            ])
+        );
+
+        let error = blocks::<Block>("1,2\nv=3\n\n4,5\nv=x\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5: invalid v \"x\": invalid digit found in string"
+        );
+        let error = blocks::<Block>("1;2\nv=3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: expected \",\", found \"1;2\"");
+        let error = blocks::<Block>("1,2\nv=3 4\n").unwrap_err();
+        assert!(matches!(error, ParseError::Line(2, _)));
+    }
+}
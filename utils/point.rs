@@ -5,7 +5,7 @@ use std::ops::AddAssign;
 use std::ops::Sub;
 use std::ops::SubAssign;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -115,3 +115,28 @@ impl SubAssign for Point {
         };
     }
 }
+
+/// The step of one cell in a direction, e.g. `(0, -1)` for up
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        Point::new(0, 0).peek(direction)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point::new(x as i32, y as i32)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i32, y as i32)
+    }
+}
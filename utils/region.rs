@@ -0,0 +1,183 @@
+use super::grid::{Grid, ALL_AROUND, ORTHOGONAL};
+
+/// Index of a region in the list given by `Grid::regions`
+pub type RegionId = usize;
+
+/// Which cells touching each other are in the same region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing a side
+    Orthogonal,
+    /// Cells sharing a side or a corner
+    AllAround,
+}
+
+/// Shape of a region of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// Number of cells
+    pub area: usize,
+    /// Number of sides of cells on the border of the region
+    pub perimeter: usize,
+    /// Number of corners of the border of the region, holes included
+    pub corners: usize,
+    /// Top left corner of the bounding box
+    pub min: (usize, usize),
+    /// Bottom right corner of the bounding box, included
+    pub max: (usize, usize),
+    /// Number of areas of other cells enclosed in the region. Around
+    /// `AllAround` regions, the cells of such an area touch by a side
+    pub holes: usize,
+}
+
+impl Region {
+    /// Number of straight sides of the border of the region, the same as
+    /// its number of corners
+    #[inline]
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Splits the grid into regions of touching cells with the same value.
+    /// Returns the region of each cell and the shape of each region
+    pub fn regions(&self, connectivity: Connectivity) -> (Grid<RegionId>, Vec<Region>) {
+        self.regions_by(connectivity, |a, b| a == b)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Same as `regions`, touching cells being in the same region when
+    /// `same` is true for their values
+    pub fn regions_by(
+        &self,
+        connectivity: Connectivity,
+        mut same: impl FnMut(&T, &T) -> bool,
+    ) -> (Grid<RegionId>, Vec<Region>) {
+        let mut labels: Grid<RegionId> = Grid::new(self.width, self.height, RegionId::MAX);
+        let mut count = 0;
+        let mut to_visit = Vec::new();
+        let mut neighbors = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if labels[(x, y)] != RegionId::MAX {
+                    continue;
+                }
+                labels[(x, y)] = count;
+                to_visit.push((x, y));
+                while let Some(cell) = to_visit.pop() {
+                    neighbors.clear();
+                    match connectivity {
+                        Connectivity::Orthogonal => {
+                            neighbors.extend(labels.orthogonal_neighbors(cell))
+                        }
+                        Connectivity::AllAround => neighbors.extend(labels.neighbors(cell)),
+                    }
+                    for &neighbor in &neighbors {
+                        if labels[neighbor] == RegionId::MAX && same(&self[cell], &self[neighbor]) {
+                            labels[neighbor] = count;
+                            to_visit.push(neighbor);
+                        }
+                    }
+                }
+                count += 1;
+            }
+        }
+
+        let regions = shapes(&labels, count, connectivity);
+        (labels, regions)
+    }
+}
+
+/// Measures the regions of a grid of labels
+fn shapes(labels: &Grid<RegionId>, count: usize, connectivity: Connectivity) -> Vec<Region> {
+    let mut regions = vec![
+        Region {
+            area: 0,
+            perimeter: 0,
+            corners: 0,
+            min: (usize::MAX, usize::MAX),
+            max: (0, 0),
+            holes: 0,
+        };
+        count
+    ];
+    // Corners seen from the inside of the region: with no neighbor on
+    // either side, with neighbors on both sides but not in the diagonal,
+    // and with a neighbor only in the diagonal
+    let mut outer = vec![0_isize; count];
+    let mut inner = vec![0_isize; count];
+    let mut diagonal = vec![0_isize; count];
+
+    for y in 0..labels.height {
+        for x in 0..labels.width {
+            let id = labels[(x, y)];
+            let inside =
+                |dx: isize, dy: isize| labels.get((x as isize + dx, y as isize + dy)) == Some(&id);
+            let region = &mut regions[id];
+            region.area += 1;
+            region.min = (region.min.0.min(x), region.min.1.min(y));
+            region.max = (region.max.0.max(x), region.max.1.max(y));
+            region.perimeter += ORTHOGONAL
+                .into_iter()
+                .filter(|&(_, dx, dy)| !inside(dx, dy))
+                .count();
+            // Each corner of the cell, as the offset of the cell in its diagonal
+            for (dx, dy) in ALL_AROUND
+                .into_iter()
+                .filter(|&(dx, dy)| dx != 0 && dy != 0)
+            {
+                match (inside(dx, 0), inside(0, dy), inside(dx, dy)) {
+                    (false, false, false) => outer[id] += 1,
+                    (false, false, true) => diagonal[id] += 1,
+                    (true, true, false) => inner[id] += 1,
+                    _ => continue,
+                }
+                region.corners += 1;
+            }
+        }
+    }
+
+    // The Euler number of a region, one minus its number of holes, comes
+    // from the number of each kind of corner. Diagonal corners are seen from
+    // both sides, and connect the cells with 8-connectivity
+    for (id, region) in regions.iter_mut().enumerate() {
+        let euler = match connectivity {
+            Connectivity::Orthogonal => (outer[id] - inner[id] + diagonal[id]) / 4,
+            Connectivity::AllAround => (outer[id] - inner[id] - diagonal[id]) / 4,
+        };
+        region.holes = (1 - euler) as usize;
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_regions() {
+        let grid: Grid<char> = Grid::from_str("AAAAB\nA.A.B\nAAAAB\n.B.BB").unwrap();
+        let (labels, regions) = grid.regions(Connectivity::Orthogonal);
+        assert_eq!(regions.len(), 7);
+        let a = regions[labels[(0, 0)]];
+        assert_eq!((a.area, a.perimeter, a.sides(), a.holes), (10, 20, 12, 1));
+        assert_eq!((a.min, a.max), ((0, 0), (3, 2)));
+        let b = regions[labels[(4, 0)]];
+        assert_eq!((b.area, b.perimeter, b.sides(), b.holes), (5, 12, 6, 0));
+        assert_ne!(labels[(1, 3)], labels[(3, 3)]);
+
+        let (_, regions) =
+            grid.regions_by(Connectivity::Orthogonal, |a, b| (*a == '.') == (*b == '.'));
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0].holes, 2);
+
+        let grid: Grid<char> = Grid::from_str(".A.\nA.A\n.A.").unwrap();
+        let (labels, regions) = grid.regions(Connectivity::AllAround);
+        assert_eq!(regions.len(), 2);
+        let a = regions[labels[(1, 0)]];
+        assert_eq!((a.area, a.perimeter, a.sides(), a.holes), (4, 16, 16, 1));
+    }
+}
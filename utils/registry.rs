@@ -0,0 +1,192 @@
+use std::any::Any;
+use std::sync::OnceLock;
+
+use super::solution::{Answer, Part};
+
+// Used by the code generated by `#[aoc]`, so the year crates do not need
+// their own dependency
+#[doc(hidden)]
+pub use inventory;
+
+/// A function solving one part of a day, registered with the `#[aoc]`
+/// attribute of `aoc_macros`:
+///
+/// ```rust,ignore
+/// #[aoc(day = 5, part = 2, name = "fast")]
+/// fn part_2_fast(input: &str) -> usize { ... }
+/// ```
+///
+/// Functions without a name are the main implementation of the part, named
+/// ones are alternatives that can be compared and benchmarked against it.
+pub struct Solution {
+    /// Crate the function is defined in, so that each year has its own registry
+    pub krate: &'static str,
+    pub day: usize,
+    pub part: Part,
+    pub name: Option<&'static str>,
+    pub solve: fn(&DayInput) -> Option<Answer>,
+}
+
+inventory::collect!(Solution);
+
+impl Solution {
+    /// Name of the implementation, for printing
+    pub fn label(&self) -> &'static str {
+        self.name.unwrap_or("default")
+    }
+}
+
+/// An input given to the registered solutions of a day. The parser of the
+/// day runs the first time a solution borrows the parsed input, and the
+/// other solutions given the same `DayInput` get the same result
+pub struct DayInput {
+    text: String,
+    parsed: OnceLock<Box<dyn Any + Send + Sync>>,
+}
+
+impl DayInput {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// The raw input
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Output of the parser of the day, parsing the input on the first call.
+    /// The parser must return owned data, to be kept with the input
+    pub fn parsed<P: Any + Send + Sync>(&self, parser: fn(&str) -> P) -> &P {
+        self.parsed
+            .get_or_init(|| Box::new(parser(&self.text)))
+            .downcast_ref()
+            .expect("the solutions of a day use the parser of that day")
+    }
+}
+
+/// The registered solutions of a crate, sorted by day, part and name.
+/// Year crates pass `env!("CARGO_CRATE_NAME")`
+pub fn solutions(krate: &str) -> Vec<&'static Solution> {
+    let mut solutions: Vec<&'static Solution> = inventory::iter::<Solution>
+        .into_iter()
+        .filter(|s| s.krate == krate)
+        .collect();
+    solutions.sort_by_key(|s| (s.day, s.part, s.name));
+    solutions
+}
+
+/// Solves both parts of a day with the main registered implementations.
+///
+/// Days written with `#[aoc]` use it for their `solve` function:
+///
+/// ```rust,ignore
+/// pub fn solve(input: &str) -> (Option<Answer>, Option<Answer>) {
+///     registry::solve(env!("CARGO_CRATE_NAME"), 5, input)
+/// }
+/// ```
+pub fn solve(krate: &str, day: usize, input: &str) -> (Option<Answer>, Option<Answer>) {
+    // Both parts share the parsed input
+    let input = DayInput::new(input);
+    let part = |part| {
+        inventory::iter::<Solution>
+            .into_iter()
+            .find(|s| s.krate == krate && s.day == day && s.part == part && s.name.is_none())
+            .and_then(|s| (s.solve)(&input))
+    };
+    (part(Part::One), part(Part::Two))
+}
+
+/// Return types accepted by `#[aoc]` functions: anything convertible to an
+/// [`Answer`], or an `Option` of it for parts that may not be solved
+pub trait IntoAnswer {
+    fn into_answer(self) -> Option<Answer>;
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),*) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Option<Answer> {
+                    Some(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer!(usize, u64, String, &str);
+
+impl<T: IntoAnswer> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Option<Answer> {
+        self.and_then(IntoAnswer::into_answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn double(input: &DayInput) -> Option<Answer> {
+        input
+            .text()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .map(|n| n * 2)
+            .into_answer()
+    }
+
+    fn double_fast(input: &DayInput) -> Option<Answer> {
+        input
+            .text()
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|n| n << 1)
+            .into_answer()
+    }
+
+    inventory::submit! {
+        Solution { krate: "registry_test", day: 3, part: Part::One, name: Some("fast"), solve: double_fast }
+    }
+    inventory::submit! {
+        Solution { krate: "registry_test", day: 3, part: Part::One, name: None, solve: double }
+    }
+
+    #[test]
+    fn test_registry() {
+        let solutions = solutions("registry_test");
+        let labels: Vec<_> = solutions.iter().map(|s| s.label()).collect();
+        assert_eq!(labels, vec!["default", "fast"]);
+        assert_eq!(
+            (solutions[1].solve)(&DayInput::new("21")),
+            Some(Answer::Number(42))
+        );
+
+        assert_eq!(
+            solve("registry_test", 3, "21"),
+            (Some(Answer::Number(42)), None)
+        );
+        assert_eq!(solve("registry_test", 4, "21"), (None, None));
+        assert!(super::solutions("other_crate").is_empty());
+    }
+
+    #[test]
+    fn test_parse_once() {
+        static PARSED: AtomicUsize = AtomicUsize::new(0);
+        fn parse(input: &str) -> Vec<u64> {
+            PARSED.fetch_add(1, Ordering::Relaxed);
+            input.split(',').map(|n| n.parse().unwrap()).collect()
+        }
+
+        let input = DayInput::new("1,2,3");
+        let sum: u64 = input.parsed(parse).iter().sum();
+        let max = input.parsed(parse).iter().max().copied();
+        assert_eq!((sum, max), (6, Some(3)));
+        assert_eq!(PARSED.load(Ordering::Relaxed), 1);
+    }
+}
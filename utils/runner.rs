@@ -0,0 +1,425 @@
+use std::fmt::Display;
+use std::process::ExitCode;
+use std::sync::{mpsc, Arc};
+
+use serde::Serialize;
+use tracing::{debug, info_span, Span};
+
+use super::config::{Config, OutputFormat};
+use super::input;
+use super::memoize::{self, CacheStats};
+use super::registry::{DayInput, Solution};
+use super::solution::Part;
+
+/// Command line flags choosing the days to run, to flatten into the
+/// arguments of the year binaries. Used by `solve_days!`
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct DaysArgs {
+    /// Runs all the days
+    #[arg(short, long)]
+    pub all: bool,
+
+    /// Days to run, can be repeated
+    #[arg(long, short)]
+    pub days: Vec<usize>,
+}
+
+impl DaysArgs {
+    /// Whether the day should be run
+    pub fn selects(&self, day: usize) -> bool {
+        self.all || self.days.contains(&day)
+    }
+}
+
+/// Solutions run on their own thread so that we can give up on them. Some are
+/// recursive, so we give them plenty of stack.
+const SOLVER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Answers of both parts of a day, formatted for printing and comparison
+type Answers = (Option<String>, Option<String>);
+
+/// Runs the days of a year using the settings from the [`Config`]:
+/// loads the inputs, applies the timeout, prints the results and optionally
+/// compares them with the expected answers.
+///
+/// Alternative implementations registered with `#[aoc(name = "...")]` are
+/// run after the `solve` function of their day, on the same inputs, parsed
+/// once for all of them.
+///
+pub struct Runner {
+    year: u16,
+    config: Config,
+    solutions: Vec<&'static Solution>,
+    failed: bool,
+    /// A solution timed out, its thread cannot be killed and keeps running
+    abandoned: bool,
+}
+
+/// How a solution did on an input, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Outcome {
+    /// The results match the expected answers
+    Passed,
+    /// Solved, but there were no answers to compare the results with
+    Unchecked,
+    /// Not solved, or a result is not the expected answer
+    Failed,
+}
+
+/// What to print for a day in JSON mode
+#[derive(Serialize)]
+struct DayReport<'a> {
+    year: u16,
+    day: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    implementation: Option<&'a str>,
+    part1: Option<&'a str>,
+    part2: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<bool>,
+}
+
+impl Runner {
+    pub fn new(year: u16, config: Config) -> Self {
+        Self {
+            year,
+            config,
+            solutions: Vec::new(),
+            failed: false,
+            abandoned: false,
+        }
+    }
+
+    /// Gives the solutions registered in the year crate, to also run the
+    /// alternative implementations
+    pub fn with_solutions(mut self, solutions: Vec<&'static Solution>) -> Self {
+        self.solutions = solutions;
+        self
+    }
+
+    /// Loads the input of the day, solves it and prints the results
+    pub fn run_day<A: Display + 'static, B: Display + 'static>(
+        &mut self,
+        day: usize,
+        solve: fn(&str) -> (Option<A>, Option<B>),
+    ) {
+        if self.config.all_inputs {
+            return self.run_all_inputs(day, solve);
+        }
+
+        let input = input::load_input(&self.config, self.year, day).map_err(|e| e.to_string());
+        let expected = match self.config.check {
+            true => expected(day, input::load_answers(&self.config, self.year, day)),
+            false => (None, None),
+        };
+
+        self.solve_input(day, None, input, expected, solve);
+    }
+
+    /// Solves the day with an input embedded in the binary instead of the one
+    /// from the input folder. The expected answers still come from there
+    pub fn run_embedded<A: Display + 'static, B: Display + 'static>(
+        &mut self,
+        day: usize,
+        input: input::EmbeddedInput,
+        solve: fn(&str) -> (Option<A>, Option<B>),
+    ) {
+        // The compile warning of include_input! is hidden by -Awarnings
+        if let input::EmbeddedInput::Missing(path) = input {
+            eprintln!(
+                "Day {}: warning: {} was missing when building, rebuild after adding it",
+                day, path
+            );
+        }
+        let input = input.get().map(str::to_string).map_err(|e| e.to_string());
+        let expected = match self.config.check {
+            true => expected(day, input::load_answers(&self.config, self.year, day)),
+            false => (None, None),
+        };
+
+        self.solve_input(day, None, input, expected, solve);
+    }
+
+    /// Solves the main input of the day and all the inputs from other
+    /// accounts, comparing each of them with its own answers. Reports the
+    /// inputs on which the solution fails, and the ones without answers
+    fn run_all_inputs<A: Display + 'static, B: Display + 'static>(
+        &mut self,
+        day: usize,
+        solve: fn(&str) -> (Option<A>, Option<B>),
+    ) {
+        let names = input::named_inputs(&self.config, self.year, day).unwrap_or_else(|e| {
+            eprintln!("Day {}: {}", day, e);
+            Vec::new()
+        });
+        let mut outcomes = Vec::new();
+
+        // The main input is optional here if there are others
+        match input::load_input(&self.config, self.year, day) {
+            Err(input::InputError::Missing(_)) if !names.is_empty() => {}
+            input => {
+                let name = input::named_inputs_dir_name(day);
+                let input = input.map_err(|e| e.to_string());
+                let expected = expected(day, input::load_answers(&self.config, self.year, day));
+                let outcome = self.solve_input(day, Some(&name), input, expected, solve);
+                outcomes.push((name, outcome));
+            }
+        }
+
+        for name in names {
+            let input = input::load_named_input(&self.config, self.year, day, &name)
+                .map_err(|e| e.to_string());
+            let expected = expected(
+                day,
+                input::load_named_answers(&self.config, self.year, day, &name),
+            );
+            let outcome = self.solve_input(day, Some(&name), input, expected, solve);
+            outcomes.push((name, outcome));
+        }
+
+        if self.config.output == OutputFormat::Text {
+            println!("{}", summary(day, &outcomes));
+        }
+    }
+
+    /// Solves an input with the `solve` function of the day, then with each of
+    /// the alternative implementations. Returns the worst of their outcomes
+    fn solve_input<A: Display + 'static, B: Display + 'static>(
+        &mut self,
+        day: usize,
+        name: Option<&str>,
+        input: Result<String, String>,
+        expected: Answers,
+        solve: fn(&str) -> (Option<A>, Option<B>),
+    ) -> Outcome {
+        let span = match name {
+            Some(name) => info_span!("day", day, input = name),
+            None => info_span!("day", day),
+        };
+        let answers = input.clone().and_then(|input| {
+            self.solve_with_timeout(span, move || {
+                let (part_1, part_2) = solve(&input);
+                (part_1.map(|r| r.to_string()), part_2.map(|r| r.to_string()))
+            })
+        });
+        let mut outcome = self.report(day, name, None, answers, expected.clone());
+
+        let alternatives: Vec<&'static Solution> = self
+            .solutions
+            .iter()
+            .filter(|s| s.day == day && s.name.is_some())
+            .copied()
+            .collect();
+        // The alternatives share the parsed input
+        let shared = input.map(|input| Arc::new(DayInput::new(input)));
+        for solution in alternatives {
+            let span = info_span!("day", day, input = name, implementation = solution.label());
+            let answers = shared.clone().and_then(|input| {
+                self.solve_with_timeout(span, move || {
+                    let answer = (solution.solve)(&input).map(|r| r.to_string());
+                    match solution.part {
+                        Part::One => (answer, None),
+                        Part::Two => (None, answer),
+                    }
+                })
+            });
+            // Only the part it solves is checked
+            let expected = match solution.part {
+                Part::One => (expected.0.clone(), None),
+                Part::Two => (None, expected.1.clone()),
+            };
+            outcome =
+                outcome.max(self.report(day, name, Some(solution.label()), answers, expected));
+        }
+        outcome
+    }
+
+    /// Exit code of the runner: failure if a day could not be solved or if a
+    /// result did not match the expected answer
+    pub fn exit_code(&self) -> ExitCode {
+        match self.failed {
+            true => ExitCode::FAILURE,
+            false => ExitCode::SUCCESS,
+        }
+    }
+
+    /// Solves in a thread of its own, giving up after the timeout. A thread
+    /// that timed out keeps running, sharing the core and the memoized
+    /// caches, so the runs after it are skipped rather than spoiled
+    fn solve_with_timeout(
+        &mut self,
+        span: Span,
+        solve: impl FnOnce() -> Answers + Send + 'static,
+    ) -> Result<Answers, String> {
+        if self.abandoned {
+            return Err("skipped, a solution that timed out is still running".to_string());
+        }
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .stack_size(SOLVER_STACK_SIZE)
+            .spawn(move || {
+                // Logs of the solution are tagged with the day
+                let _span = span.enter();
+                // Memoized functions start from scratch, to time the run fairly
+                memoize::clear_all();
+                let answers = solve();
+                for (name, stats) in memoize::stats() {
+                    if stats != CacheStats::default() {
+                        debug!("Cache of {}: {}", name, stats);
+                    }
+                }
+                let _ = sender.send(answers);
+            })
+            .map_err(|e| e.to_string())?;
+
+        // If the solution panics, the sender is dropped and we get an error
+        match self.config.timeout {
+            Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => {
+                    self.abandoned = true;
+                    format!(
+                        "timed out after {}s, skipping the next runs as it is still running",
+                        timeout.as_secs()
+                    )
+                }
+                mpsc::RecvTimeoutError::Disconnected => "solution panicked".to_string(),
+            }),
+            None => receiver.recv().map_err(|_| "solution panicked".to_string()),
+        }
+    }
+
+    /// Prints the results of a day, and tells if they were the expected
+    /// answers
+    fn report(
+        &mut self,
+        day: usize,
+        input: Option<&str>,
+        implementation: Option<&str>,
+        answers: Result<Answers, String>,
+        expected: Answers,
+    ) -> Outcome {
+        let (part_1, part_2, error) = match &answers {
+            Ok((part_1, part_2)) => (part_1.as_deref(), part_2.as_deref(), None),
+            Err(e) => (None, None, Some(e.as_str())),
+        };
+        let part_1_ok = check(part_1, expected.0.as_deref());
+        let part_2_ok = check(part_2, expected.1.as_deref());
+        let check = match (part_1_ok, part_2_ok) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(true) && b.unwrap_or(true)),
+        };
+        let failed = error.is_some() || check == Some(false);
+        self.failed |= failed;
+
+        match self.config.output {
+            OutputFormat::Text => {
+                let input = input.map(|i| format!(" ({})", i)).unwrap_or_default();
+                match implementation {
+                    Some(name) => println!("Solving Day {}{} with {}.", day, input, name),
+                    None => println!("Solving Day {}{}.", day, input),
+                }
+                if let Some(e) = error {
+                    eprintln!("Day {}: {}", day, e);
+                }
+                print_part(1, part_1, expected.0.as_deref());
+                print_part(2, part_2, expected.1.as_deref());
+            }
+            OutputFormat::Json => {
+                let report = DayReport {
+                    year: self.year,
+                    day,
+                    input,
+                    implementation,
+                    part1: part_1,
+                    part2: part_2,
+                    error,
+                    check,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&report).expect("report is serializable")
+                );
+            }
+        }
+
+        match (failed, check) {
+            (true, _) => Outcome::Failed,
+            (false, None) => Outcome::Unchecked,
+            (false, Some(_)) => Outcome::Passed,
+        }
+    }
+}
+
+/// Line summing up the outcomes of the inputs of a day, e.g.
+/// `Day 17: 3 passed, 1 unchecked (bob)`
+fn summary(day: usize, outcomes: &[(String, Outcome)]) -> String {
+    let names = |outcome| -> Vec<&str> {
+        outcomes
+            .iter()
+            .filter(|(_, o)| *o == outcome)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    };
+    let (unchecked, failed) = (names(Outcome::Unchecked), names(Outcome::Failed));
+    if unchecked.is_empty() && failed.is_empty() {
+        return format!("Day {} passes on all inputs.", day);
+    }
+    let mut summary = format!("Day {}: {} passed", day, names(Outcome::Passed).len());
+    for (label, names) in [("unchecked", unchecked), ("failed", failed)] {
+        if !names.is_empty() {
+            summary += &format!(", {} {} ({})", names.len(), label, names.join(", "));
+        }
+    }
+    summary
+}
+
+/// Unwraps expected answers, a broken answers file should not stop the run
+fn expected(day: usize, answers: Result<Answers, input::InputError>) -> Answers {
+    answers.unwrap_or_else(|e| {
+        eprintln!("Day {}: {}", day, e);
+        (None, None)
+    })
+}
+
+/// Compares a result with the expected answer, if there is one
+fn check(result: Option<&str>, expected: Option<&str>) -> Option<bool> {
+    expected.map(|expected| result == Some(expected))
+}
+
+fn print_part(part: usize, result: Option<&str>, expected: Option<&str>) {
+    match (result, expected) {
+        (Some(r), None) => println!("Part {} result: {}", part, r),
+        (Some(r), Some(e)) if r == e => println!("Part {} result: {} (ok)", part, r),
+        (Some(r), Some(e)) => println!("Part {} result: {} (expected {})", part, r, e),
+        (None, Some(e)) => println!("Part {} result: none (expected {})", part, e),
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let outcomes = |list: &[(&str, Outcome)]| -> Vec<(String, Outcome)> {
+            list.iter().map(|(n, o)| (n.to_string(), *o)).collect()
+        };
+        let passed = outcomes(&[("day17", Outcome::Passed), ("alice", Outcome::Passed)]);
+        assert_eq!(summary(17, &passed), "Day 17 passes on all inputs.");
+        let mixed = outcomes(&[
+            ("day17", Outcome::Passed),
+            ("alice", Outcome::Unchecked),
+            ("bob", Outcome::Failed),
+            ("carol", Outcome::Unchecked),
+        ]);
+        assert_eq!(
+            summary(17, &mixed),
+            "Day 17: 1 passed, 2 unchecked (alice, carol), 1 failed (bob)"
+        );
+    }
+}
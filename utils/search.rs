@@ -0,0 +1,351 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Result of a search from a start state: the distances to all the states
+/// that were reached, and for each state the states it can be reached from
+/// on a shortest route, so that every shortest path can be recovered
+///
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    pub start: S,
+    /// Distance from the start to each state that was reached
+    pub distances: HashMap<S, usize>,
+    /// States just before each state on its shortest routes. The start has
+    /// none
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// Goal states reached at the shortest distance, empty if no goal was
+    /// reached
+    pub goals: Vec<S>,
+}
+
+impl<S: Hash + Eq + Clone> Search<S> {
+    fn new(start: S) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), 0)]),
+            predecessors: HashMap::new(),
+            goals: Vec::new(),
+            start,
+        }
+    }
+
+    /// Distance from the start to a state, if it was reached
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// Distance from the start to the closest goal
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.distance(self.goals.first()?)
+    }
+
+    /// States just before a state on its shortest routes
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the shortest paths from the start to the closest goal, both
+    /// included
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    /// One of the shortest paths from the start to a state, both included
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All the shortest paths from the start to the goals. Moves must cost
+    /// more than 0 for the paths to be counted, `count` may be `None`
+    /// otherwise
+    pub fn best_paths(&self) -> BestPaths<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut to_visit = self.goals.clone();
+        while let Some(state) = to_visit.pop() {
+            for previous in self.predecessors(&state) {
+                if states.insert(previous.clone()) {
+                    to_visit.push(previous.clone());
+                }
+            }
+        }
+
+        // Each state is reached by the sum of the paths to its predecessors,
+        // which are all closer to the start. With moves costing 0, a
+        // predecessor at the same distance may not be counted yet
+        let mut by_distance: Vec<&S> = states.iter().collect();
+        by_distance.sort_by_key(|state| self.distances[*state]);
+        let mut paths: HashMap<&S, Option<usize>> = HashMap::new();
+        for state in by_distance {
+            let count = match state == &self.start {
+                true => Some(1),
+                false => self
+                    .predecessors(state)
+                    .iter()
+                    .try_fold(0_usize, |total, previous| {
+                        total.checked_add(paths.get(previous).copied().flatten()?)
+                    }),
+            };
+            paths.insert(state, count);
+        }
+        let count = self.goals.iter().try_fold(0_usize, |total, goal| {
+            total.checked_add(paths.get(goal).copied().flatten()?)
+        });
+
+        BestPaths { states, count }
+    }
+
+    /// Records that `state` is reached from `from` at `distance`. Returns
+    /// true if this is a shorter route than the known ones, i.e. the state
+    /// has to be explored (again)
+    fn reach(&mut self, from: &S, state: S, distance: usize) -> bool {
+        match self.distances.entry(state.clone()) {
+            Entry::Occupied(mut known) => match distance.cmp(known.get()) {
+                Ordering::Less => {
+                    known.insert(distance);
+                    self.predecessors.insert(state, vec![from.clone()]);
+                    true
+                }
+                Ordering::Equal => {
+                    self.predecessors
+                        .entry(state)
+                        .or_default()
+                        .push(from.clone());
+                    false
+                }
+                Ordering::Greater => false,
+            },
+            Entry::Vacant(unknown) => {
+                unknown.insert(distance);
+                self.predecessors.insert(state, vec![from.clone()]);
+                true
+            }
+        }
+    }
+}
+
+/// The states on at least one of the shortest paths to a goal, and how many
+/// such paths there are
+///
+#[derive(Debug, Clone)]
+pub struct BestPaths<S> {
+    pub states: HashSet<S>,
+    /// Number of distinct shortest paths, `None` if it does not fit in a
+    /// `usize` or if some moves cost 0
+    pub count: Option<usize>,
+}
+
+impl<S> BestPaths<S> {
+    /// The distinct positions on the paths, for states made of a position
+    /// and more, e.g. a direction
+    pub fn positions<P: Hash + Eq>(&self, position: impl Fn(&S) -> P) -> HashSet<P> {
+        self.states.iter().map(position).collect()
+    }
+}
+
+/// Breadth-first search, for moves that all cost 1. Stops once all the
+/// goals at the shortest distance have been found, or explores everything
+/// that can be reached if `is_goal` never matches
+///
+/// ```
+/// use aoc_utils::search::bfs;
+///
+/// let search = bfs(0, |&n| [n + 1, n * 2], |&n| n == 10);
+/// assert_eq!(search.goal_distance(), Some(5));
+/// assert_eq!(search.path(), Some(vec![0, 1, 2, 4, 5, 10]));
+/// ```
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = queue.pop_front() {
+        if search.goal_distance().is_some_and(|d| distance > d) {
+            break;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for next in neighbors(&state) {
+            if search.reach(&state, next.clone(), distance + 1) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's search, for moves of any cost given by `neighbors` along with
+/// the next states. Stops like `bfs`
+///
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, neighbors, |_| 0, is_goal)
+}
+
+/// A* search, Dijkstra's search guided towards the goals by `heuristic`.
+/// The heuristic must never overestimate the distance to the closest goal,
+/// and must not decrease by more than the cost of a move, otherwise the
+/// distances may not be the shortest ones
+///
+pub fn astar<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = BinaryHeap::from([Queued {
+        estimate: heuristic(&start),
+        distance: 0,
+        state: start,
+    }]);
+    while let Some(Queued {
+        estimate,
+        distance,
+        state,
+    }) = queue.pop()
+    {
+        if search.goal_distance().is_some_and(|d| estimate > d) {
+            break;
+        }
+        // The state was queued again since, with a shorter distance
+        if search.distances[&state] < distance {
+            continue;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for (next, cost) in neighbors(&state) {
+            if search.reach(&state, next.clone(), distance + cost) {
+                queue.push(Queued {
+                    estimate: distance + cost + heuristic(&next),
+                    distance: distance + cost,
+                    state: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+/// A state waiting to be explored. Ordered so that the binary heap gives the
+/// lowest estimate first
+struct Queued<S> {
+    estimate: usize,
+    distance: usize,
+    state: S,
+}
+
+impl<S> PartialEq for Queued<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for Queued<S> {}
+
+impl<S> PartialOrd for Queued<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Queued<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::Point;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_searches() {
+        let maze: Grid<char> = Grid::from_str("S.#\n..#\n#.E").unwrap();
+        let moves = |p: &Point| {
+            maze.orthogonal_cells(p.as_usize_tuple())
+                .filter(|(_, _, &c)| c != '#')
+                .map(|(_, next, _)| next)
+                .collect::<Vec<_>>()
+        };
+        let goal = Point::new(2, 2);
+
+        let search = bfs(Point::new(0, 0), moves, |&p| p == goal);
+        assert_eq!(search.goal_distance(), Some(4));
+        assert_eq!(search.predecessors(&Point::new(1, 1)).len(), 2);
+        assert_eq!(search.path().map(|p| p.len()), Some(5));
+
+        let weighted = |p: &Point| moves(p).into_iter().map(|n| (n, 1 + n.y as usize * 10));
+        let search = dijkstra(Point::new(0, 0), weighted, |&p| p == goal);
+        assert_eq!(search.goal_distance(), Some(54));
+        assert_eq!(
+            search.path(),
+            Some(vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(1, 1),
+                Point::new(1, 2),
+                Point::new(2, 2)
+            ])
+        );
+
+        let manhattan = |p: &Point| (goal.x - p.x + goal.y - p.y) as usize;
+        let search = astar(Point::new(0, 0), weighted, manhattan, |&p| p == goal);
+        assert_eq!(search.goal_distance(), Some(54));
+        assert_eq!(search.distance(&Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_best_paths() {
+        let down_right = |&(x, y): &(u32, u32)| [(x + 1, y), (x, y + 1)];
+        let best = bfs((0, 0), down_right, |&p| p == (2, 2)).best_paths();
+        assert_eq!(best.count, Some(6));
+        assert_eq!(best.states.len(), 9);
+        assert_eq!(best.positions(|&(x, _)| x).len(), 3);
+
+        let best = bfs((0, 0), down_right, |&p| p == (10, 10)).best_paths();
+        assert_eq!(best.count, Some(184756));
+        let best = bfs((0, 0), down_right, |&p| p == (40, 40)).best_paths();
+        assert_eq!(best.count, None);
+
+        // Free moves along a line, then one paid move to the goal
+        let free = |&n: &u32| match n {
+            20 => vec![(21, 1)],
+            n if n < 20 => vec![(n + 1, 0)],
+            _ => vec![],
+        };
+        let best = dijkstra(0, free, |&n| n == 21).best_paths();
+        assert_eq!(best.states.len(), 22);
+        assert!(matches!(best.count, None | Some(1)));
+    }
+}
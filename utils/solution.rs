@@ -0,0 +1,157 @@
+use std::panic::{self, AssertUnwindSafe};
+
+/// One of the two parts of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<usize> for Part {
+    type Error = SolveError;
+
+    fn try_from(part: usize) -> Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to a part of a puzzle. Most of them are numbers, a few are text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Prints the answer the way it should be submitted
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Errors when running a solution on some input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// There is no such day in this year
+    UnknownDay(usize),
+    /// Puzzles only have 2 parts
+    UnknownPart(usize),
+    /// The day exists but this part has no solution yet
+    NotImplemented(usize, Part),
+    /// The solution panicked, most likely on an input it could not parse
+    Panicked(String),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "no solution for day {}", day),
+            SolveError::UnknownPart(part) => write!(f, "no part {}, only 1 and 2", part),
+            SolveError::NotImplemented(day, part) => {
+                write!(f, "day {} part {} is not solved yet", day, part)
+            }
+            SolveError::Panicked(message) => write!(f, "solution panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Runs the `solve` function of a day on an input and returns the answer to
+/// one of the parts. Days solve both parts at once, so both are computed.
+///
+/// Panics in the solution are caught and returned as errors, so that bad
+/// input does not take down the caller.
+pub fn run<A, B>(
+    day: usize,
+    solve: fn(&str) -> (Option<A>, Option<B>),
+    part: Part,
+    input: &str,
+) -> Result<Answer, SolveError>
+where
+    A: Into<Answer>,
+    B: Into<Answer>,
+{
+    let (part_1, part_2) =
+        panic::catch_unwind(AssertUnwindSafe(|| solve(input))).map_err(|payload| {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "unknown reason".to_string(),
+                },
+            };
+            SolveError::Panicked(message)
+        })?;
+
+    let answer = match part {
+        Part::One => part_1.map(Into::into),
+        Part::Two => part_2.map(Into::into),
+    };
+    answer.ok_or(SolveError::NotImplemented(day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(input: &str) -> (Option<usize>, Option<String>) {
+        let number: usize = input.trim().parse().expect("Input should be a number");
+        (Some(number * 2), None)
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run(1, solve, Part::One, "21"), Ok(Answer::Number(42)));
+        assert_eq!(
+            run(1, solve, Part::Two, "21"),
+            Err(SolveError::NotImplemented(1, Part::Two))
+        );
+        assert!(matches!(
+            run(1, solve, Part::One, "abc"),
+            Err(SolveError::Panicked(m)) if m.contains("Input should be a number")
+        ));
+        assert_eq!(Part::try_from(3), Err(SolveError::UnknownPart(3)));
+        assert_eq!(Answer::from("4,6,3").to_string(), "4,6,3");
+    }
+}
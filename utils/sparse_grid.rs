@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use super::direction::Direction;
+use super::grid::{Grid, ALL_AROUND};
+use super::point::Point;
+
+/// A grid without bounds, storing only the cells that were set. The other
+/// ones have the default value. Keeps track of the bounding box of the set
+/// cells, e.g. to display them or to turn them into a dense `Grid`
+///
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    /// Value of the cells that are not set
+    pub default: T,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// Number of cells that are set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Checks if a cell is set
+    #[inline]
+    pub fn contains(&self, point: impl Into<Point>) -> bool {
+        self.cells.contains_key(&point.into())
+    }
+
+    /// Value of a cell, the default one if it is not set
+    #[inline]
+    pub fn get(&self, point: impl Into<Point>) -> &T {
+        self.cells.get(&point.into()).unwrap_or(&self.default)
+    }
+
+    /// Sets a cell, returning its previous value if it was set
+    pub fn insert(&mut self, point: impl Into<Point>, value: T) -> Option<T> {
+        let point = point.into();
+        self.extend_bounds(point);
+        self.cells.insert(point, value)
+    }
+
+    /// Unsets a cell, returning its value if it was set
+    pub fn remove(&mut self, point: impl Into<Point>) -> Option<T> {
+        let point = point.into();
+        let value = self.cells.remove(&point)?;
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.bounds = None;
+                let points: Vec<Point> = self.cells.keys().copied().collect();
+                points.into_iter().for_each(|p| self.extend_bounds(p));
+            }
+        }
+        Some(value)
+    }
+
+    fn extend_bounds(&mut self, point: Point) {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+        });
+    }
+
+    /// Top left and bottom right corners of the cells that are set, both
+    /// included
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Width of the bounding box of the cells that are set
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    /// Height of the bounding box of the cells that are set
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// Cells that are set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// Positions of the neighbors of a cell, diagonals included
+    pub fn neighbors(&self, point: impl Into<Point>) -> impl Iterator<Item = Point> {
+        let point = point.into();
+        ALL_AROUND
+            .into_iter()
+            .map(move |(dx, dy)| point + Point::new(dx as i32, dy as i32))
+    }
+
+    /// Positions of the neighbors of a cell, without the diagonals
+    pub fn orthogonal_neighbors(&self, point: impl Into<Point>) -> impl Iterator<Item = Point> {
+        let point = point.into();
+        Direction::all().into_iter().map(move |d| point.peek(d))
+    }
+
+    /// Neighbors of a cell without the diagonals, with the direction to each
+    /// of them and their value
+    pub fn orthogonal_cells(
+        &self,
+        point: impl Into<Point>,
+    ) -> impl Iterator<Item = (Direction, Point, &T)> + '_ {
+        let point = point.into();
+        Direction::all()
+            .into_iter()
+            .map(move |d| (d, point.peek(d), self.get(point.peek(d))))
+    }
+
+    /// Neighbors of a cell, diagonals included, with their value
+    pub fn neighbor_cells(
+        &self,
+        point: impl Into<Point>,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.neighbors(point.into()).map(move |p| (p, self.get(p)))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Mutable value of a cell, which is set to the default value first if
+    /// it is not set yet
+    pub fn get_mut(&mut self, point: impl Into<Point>) -> &mut T {
+        let point = point.into();
+        if !self.cells.contains_key(&point) {
+            self.insert(point, self.default.clone());
+        }
+        self.cells.get_mut(&point).unwrap()
+    }
+
+    /// Dense grid of the bounding box of the cells that are set, and the
+    /// position of its top left corner
+    pub fn to_grid(&self) -> (Grid<T>, Point) {
+        let Some((min, max)) = self.bounds() else {
+            let empty = Grid {
+                width: 0,
+                height: 0,
+                data: Vec::new(),
+            };
+            return (empty, Point::new(0, 0));
+        };
+        let data = (min.y..=max.y)
+            .flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+            .map(|p| self.get(p).clone())
+            .collect();
+        let grid = Grid {
+            width: self.width(),
+            height: self.height(),
+            data,
+        };
+        (grid, min)
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    /// Sparse grid of the cells of a dense one, except the ones with the
+    /// default value
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self {
+        let mut sparse = Self::new(default);
+        for (y, row) in grid.rows().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                if *value != sparse.default {
+                    sparse.insert((x, y), value.clone());
+                }
+            }
+        }
+        sparse
+    }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+///
+/// Using any coordinates to lookup a value, the default one if it is not set
+///
+impl<T, P: Into<Point>> Index<P> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: P) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+///
+/// Using any coordinates to set a value
+///
+impl<T: Clone, P: Into<Point>> IndexMut<P> for SparseGrid<T> {
+    fn index_mut(&mut self, point: P) -> &mut Self::Output {
+        self.get_mut(point)
+    }
+}
+
+/// Prints the bounding box of the cells that are set
+impl<T: std::fmt::Display> std::fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{}", self.get((x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut sand = SparseGrid::new('.');
+        assert_eq!(sand.bounds(), None);
+        sand[(500, 0)] = '+';
+        sand.insert((498, -2), 'o');
+        sand.insert((-3, 4), '#');
+        assert_eq!(
+            sand.bounds(),
+            Some((Point::new(-3, -2), Point::new(500, 4)))
+        );
+        assert_eq!(sand[(0, 0)], '.');
+        assert_eq!(
+            sand.orthogonal_cells((500, 1))
+                .filter(|c| *c.2 == '+')
+                .count(),
+            1
+        );
+
+        assert_eq!(
+            sand.neighbor_cells((499, -1))
+                .filter(|c| *c.1 != '.')
+                .count(),
+            2
+        );
+
+        sand.remove((-3, 4));
+        assert_eq!(
+            sand.bounds(),
+            Some((Point::new(498, -2), Point::new(500, 0)))
+        );
+        assert_eq!(sand.to_string(), "o..\n...\n..+\n");
+
+        let (grid, origin) = sand.to_grid();
+        assert_eq!(
+            (grid.to_string(), origin),
+            (sand.to_string(), Point::new(498, -2))
+        );
+
+        let grid: Grid<char> = Grid::from_str("..#\n#..").unwrap();
+        let walls = SparseGrid::from_grid(&grid, '.');
+        assert_eq!(walls.len(), 2);
+        assert_eq!(walls.to_grid().0.to_string(), "..#\n#..\n");
+
+        let mut single = SparseGrid::new(0);
+        single[(3, 3)] += 1;
+        single.remove((3, 3));
+        assert_eq!((single.bounds(), single.width()), (None, 0));
+    }
+}
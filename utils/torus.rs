@@ -0,0 +1,145 @@
+use std::ops::{Index, IndexMut};
+
+use super::grid::Grid;
+use super::point::Point;
+
+/// A grid whose opposite sides touch: stepping out of one side comes back
+/// in from the other one, and any coordinates are wrapped into the grid
+///
+#[derive(Debug, Clone)]
+pub struct TorusGrid<T> {
+    pub grid: Grid<T>,
+}
+
+impl<T> TorusGrid<T> {
+    /// Panics if the grid is empty, as no coordinates could be wrapped into
+    /// it
+    pub fn from_grid(grid: Grid<T>) -> Self {
+        assert!(
+            grid.width > 0 && grid.height > 0,
+            "a torus cannot be made from an empty grid"
+        );
+        Self { grid }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.grid.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.grid.height
+    }
+
+    /// The coordinates of the cell in the grid at any coordinates
+    #[inline]
+    pub fn wrap(&self, point: impl Into<Point>) -> Point {
+        let point = point.into();
+        Point::new(
+            point.x.rem_euclid(self.width() as i32),
+            point.y.rem_euclid(self.height() as i32),
+        )
+    }
+
+    /// Where one step leads from a point, e.g. a `Direction` or a velocity
+    #[inline]
+    pub fn step(&self, from: impl Into<Point>, step: impl Into<Point>) -> Point {
+        self.wrap(from.into() + step.into())
+    }
+
+    /// Where `n` steps lead from a point, without taking each of them
+    pub fn step_n(&self, from: impl Into<Point>, step: impl Into<Point>, n: usize) -> Point {
+        let (from, step) = (from.into(), step.into());
+        // Everything is reduced modulo the size first so that it cannot
+        // overflow, however far it goes
+        let along = |from: i32, step: i32, size: usize| {
+            let steps = (step as i64).rem_euclid(size as i64) * (n % size) as i64;
+            (from as i64 + steps).rem_euclid(size as i64) as i32
+        };
+        Point::new(
+            along(from.x, step.x, self.width()),
+            along(from.y, step.y, self.height()),
+        )
+    }
+
+    /// Value of the cell at any coordinates
+    #[inline]
+    pub fn get(&self, point: impl Into<Point>) -> &T {
+        let (x, y) = self.wrap(point).as_usize_tuple();
+        &self.grid.data[y * self.grid.width + x]
+    }
+
+    /// Mutable value of the cell at any coordinates
+    #[inline]
+    pub fn get_mut(&mut self, point: impl Into<Point>) -> &mut T {
+        let (x, y) = self.wrap(point).as_usize_tuple();
+        &mut self.grid.data[y * self.grid.width + x]
+    }
+}
+
+impl<T: Copy> TorusGrid<T> {
+    /// Create a new torus with the indicated dimensions, and filled with the
+    /// given value
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_grid(Grid::new(width, height, value))
+    }
+}
+
+impl<T> From<Grid<T>> for TorusGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid(grid)
+    }
+}
+
+///
+/// Using any coordinates to lookup a value, wrapped into the grid
+///
+impl<T, P: Into<Point>> Index<P> for TorusGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: P) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for TorusGrid<T> {
+    fn index_mut(&mut self, point: P) -> &mut Self::Output {
+        self.get_mut(point)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for TorusGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    #[test]
+    fn test_torus() {
+        let mut torus = TorusGrid::new(11, 7, 0);
+        assert_eq!(torus.wrap((-1, 7)), Point::new(10, 0));
+        assert_eq!(torus.step((0, 0), Direction::Left), Point::new(10, 0));
+
+        let mut robot = Point::new(2, 4);
+        for _ in 0..5 {
+            robot = torus.step(robot, (2, -3));
+        }
+        assert_eq!(robot, Point::new(1, 3));
+        assert_eq!(torus.step_n((2, 4), (2, -3), 5), robot);
+        assert_eq!(torus.step_n((2, 4), (2, -3), 77), Point::new(2, 4));
+        assert_eq!(
+            torus.step_n((2, 4), (i32::MAX, i32::MIN), usize::MAX),
+            torus.step_n((2, 4), (i32::MAX % 11, i32::MIN % 7), usize::MAX % 77)
+        );
+
+        torus[(-1, -1)] = 5;
+        assert_eq!(torus.grid[(10, 6)], 5);
+        assert_eq!(torus[robot + Point::new(9, 3)], 5);
+    }
+}